@@ -0,0 +1,65 @@
+//! Float functions that live in std rather than core. When the `std` feature is off these are provided by `libm` instead,
+//! under the same names, so the rest of the crate can call them as methods either way.
+
+pub(crate) trait Float: Sized {
+    fn sqrt(self) -> Self;
+    fn powf(self, n: Self) -> Self;
+    fn powi(self, n: i32) -> Self;
+    fn exp(self) -> Self;
+    fn exp_m1(self) -> Self;
+    fn ln(self) -> Self;
+    fn ln_1p(self) -> Self;
+    fn hypot(self, other: Self) -> Self;
+    fn sin(self) -> Self;
+    fn cos(self) -> Self;
+    fn sin_cos(self) -> (Self, Self);
+    fn floor(self) -> Self;
+    fn ceil(self) -> Self;
+    fn round(self) -> Self;
+    fn fract(self) -> Self;
+    fn rem_euclid(self, rhs: Self) -> Self;
+}
+
+impl Float for f32 {
+    fn sqrt(self) -> f32 { libm::sqrtf(self) }
+    fn powf(self, n: f32) -> f32 { libm::powf(self, n) }
+    fn powi(self, n: i32) -> f32 { libm::powf(self, n as f32) }
+    fn exp(self) -> f32 { libm::expf(self) }
+    fn exp_m1(self) -> f32 { libm::expm1f(self) }
+    fn ln(self) -> f32 { libm::logf(self) }
+    fn ln_1p(self) -> f32 { libm::log1pf(self) }
+    fn hypot(self, other: f32) -> f32 { libm::hypotf(self, other) }
+    fn sin(self) -> f32 { libm::sinf(self) }
+    fn cos(self) -> f32 { libm::cosf(self) }
+    fn sin_cos(self) -> (f32, f32) { libm::sincosf(self) }
+    fn floor(self) -> f32 { libm::floorf(self) }
+    fn ceil(self) -> f32 { libm::ceilf(self) }
+    fn round(self) -> f32 { libm::roundf(self) }
+    fn fract(self) -> f32 { self - libm::truncf(self) }
+    fn rem_euclid(self, rhs: f32) -> f32 {
+        let r: f32 = self % rhs;
+        if r < 0.0 { r + rhs.abs() } else { r }
+    }
+}
+
+impl Float for f64 {
+    fn sqrt(self) -> f64 { libm::sqrt(self) }
+    fn powf(self, n: f64) -> f64 { libm::pow(self, n) }
+    fn powi(self, n: i32) -> f64 { libm::pow(self, n as f64) }
+    fn exp(self) -> f64 { libm::exp(self) }
+    fn exp_m1(self) -> f64 { libm::expm1(self) }
+    fn ln(self) -> f64 { libm::log(self) }
+    fn ln_1p(self) -> f64 { libm::log1p(self) }
+    fn hypot(self, other: f64) -> f64 { libm::hypot(self, other) }
+    fn sin(self) -> f64 { libm::sin(self) }
+    fn cos(self) -> f64 { libm::cos(self) }
+    fn sin_cos(self) -> (f64, f64) { libm::sincos(self) }
+    fn floor(self) -> f64 { libm::floor(self) }
+    fn ceil(self) -> f64 { libm::ceil(self) }
+    fn round(self) -> f64 { libm::round(self) }
+    fn fract(self) -> f64 { self - libm::trunc(self) }
+    fn rem_euclid(self, rhs: f64) -> f64 {
+        let r: f64 = self % rhs;
+        if r < 0.0 { r + rhs.abs() } else { r }
+    }
+}
@@ -7,8 +7,7 @@ pub mod keys_table {
     /// 
     /// Sometime down the road I'd like to be able to generate this at runtime. Right now it's recommended to
     /// pick some to use for your application. Please don't embed the table itself if you don't have to, it's very large. 
-
-    const KEYS_TABLE: [u64; 8192] = [
+    pub(crate) static KEYS_TABLE: [u64; 8192] = [
         0x2467cb532b5ce8d1,
         0x4a8dfb965c72184f,
         0x87b2ad565c7426ab,
@@ -8,10 +8,31 @@
 //! The algorithm is explained in this paper
 //! <https://arxiv.org/pdf/2004.06278v3.pdf>
 #![crate_type = "lib"]
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+#[cfg(all(test, not(feature = "std")))]
+#[macro_use]
+extern crate std;
+
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+
+#[cfg(not(any(feature = "std", feature = "libm")))]
+compile_error!("squares-rng needs the std feature, or the libm feature for float math without std");
+
+#[cfg(all(not(feature = "std"), feature = "libm"))]
+mod float;
+#[cfg(all(not(feature = "std"), feature = "libm"))]
+use float::Float;
 
 #[cfg(feature = "keys_table")]
-mod keys_table;
+mod keys;
+#[cfg(feature = "keys_table")]
+use keys::keys_table::KEYS_TABLE;
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SquaresRNG {
     pub key: u64,
     pub counter: u64,
@@ -19,7 +40,14 @@ pub struct SquaresRNG {
 
 impl SquaresRNG {
     const U64_REMAINDER: u64 = u32::MAX as u64;
+    const CORRIDOR_LOOP_FRACTION: f64 = 0.15;
+    #[cfg(test)]
     const TEST_COUNT: u64 = 10_000_000;
+    /// Most dice SquaresRNG::roll will roll at once, since each die takes its own draw.
+    pub const MAX_DICE: u64 = 10_000;
+    /// Newest algorithm version SquaresRNG::shuffle_versioned knows. Versions 1 through this are supported.
+    pub const CURRENT_SHUFFLE_VERSION: u8 = 1;
+    /// First key of the keys table. Used by Default and the tests.
     const TEST_KEY: u64 = 0x2467cb532b5ce8d1;
 
     /// Requires a key to be picked for number generation. A unique combo of counter and key will provide the same number each time.
@@ -29,7 +57,7 @@ impl SquaresRNG {
     #[cfg(feature = "keys_table")]
     pub fn new(counter: u64, key: u64) -> SquaresRNG {
         SquaresRNG {
-            key: KEYS_TABLE[key % KEYS_TABLE.len()],
+            key: KEYS_TABLE[(key % KEYS_TABLE.len() as u64) as usize],
             counter,
         }
     }
@@ -44,28 +72,102 @@ impl SquaresRNG {
         SquaresRNG { key, counter }
     }
 
-    /// Returns a random 64-bit unsigned integer
-    /// The output is uniform and will eventually produce all 2^64 numbers with the key.
+    /// Same as new_with_key, but returns an error instead if the key fails validate_key.
+    pub fn new_with_key_checked(counter: u64, key: u64) -> Result<SquaresRNG, KeyError> {
+        match SquaresRNG::key_error(key) {
+            Some(error) => Err(error),
+            None => Ok(SquaresRNG::new_with_key(counter, key)),
+        }
+    }
+
+    /// Returns true if the key looks usable for number generation. The key needs a roughly even count of 0 bits and 1 bits
+    /// (between 24 and 44 ones), and can't be a simple pattern like long runs of the same bit or a repeating byte.
+    /// Every key in the keys table passes.
+    pub fn validate_key(key: u64) -> bool {
+        SquaresRNG::key_error(key).is_none()
+    }
+
+    /// Returns what is wrong with a key, if anything. See validate_key.
+    fn key_error(key: u64) -> Option<KeyError> {
+        let ones: u32 = key.count_ones();
+        if !(24..=44).contains(&ones) {
+            return Some(KeyError::Unbalanced(ones));
+        }
+
+        // Table keys flip between 0 and 1 at least 22 times going around the key. Repeating every 4, 8, 16, or 32 bits
+        // means the rounds keep mixing the same few bits.
+        let flips: u32 = (key ^ key.rotate_right(1)).count_ones();
+        let repeating: bool = [4, 8, 16, 32].iter().any(|&period| key == key.rotate_right(period));
+        if flips < 16 || repeating {
+            return Some(KeyError::Patterned);
+        }
+
+        None
+    }
+
+    /// One step of the generator: the 32-bit output (the high half of its last round) for the current counter, in a u64.
+    /// Increments the counter by one.
     //#[inline]
-    fn rand_u64(&mut self) -> u64 {
+    fn step(&mut self) -> u64 {
         let mut x: u64 = u64::wrapping_mul(self.counter, self.key);
         let y: u64 = x;
         let z: u64 = u64::wrapping_add(y, self.key);
 
-        x = u64::wrapping_add(u64::wrapping_mul(x,x), y); x = (x >> 32) | (x << 32);
-        x = u64::wrapping_add(u64::wrapping_mul(x,x), z); x = (x >> 32) | (x << 32);
-        x = u64::wrapping_add(u64::wrapping_mul(x,x), y); x = (x >> 32) | (x << 32);
+        x = u64::wrapping_add(u64::wrapping_mul(x,x), y); x = x.rotate_right(32);
+        x = u64::wrapping_add(u64::wrapping_mul(x,x), z); x = x.rotate_right(32);
+        x = u64::wrapping_add(u64::wrapping_mul(x,x), y); x = x.rotate_right(32);
 
-        self.counter += 1;
+        self.counter = self.counter.wrapping_add(1);
         
-        return (u64::wrapping_add(u64::wrapping_mul(x,x), z) >> 32) as u64;
+        u64::wrapping_add(u64::wrapping_mul(x,x), z) >> 32
     }
 
-    /// Returns a random usize within a range. For picking random elements in an array or Vec.
+    /// Returns the raw 32-bit output of the generator (the high 32 bits of its last round) without going through the float path.
+    /// Increments the counter by one. A given key and counter will always produce the same output, so consecutive calls walk
+    /// through the key's sequence in order.
+    //#[inline]
+    pub fn rand_u32(&mut self) -> u32 {
+        self.step() as u32
+    }
+
+    /// Returns a random u64 with all 64 bits random, made from two rand_u32 outputs with the first in the high bits.
+    /// Increments the counter by two.
+    //#[inline]
+    pub fn rand_u64(&mut self) -> u64 {
+        (self.step() << 32) | self.step()
+    }
+
+    /// Returns a random usize in 0..size. For picking random elements in an array or Vec. Every index is equally likely.
+    /// Returns 0 if size is 0.
     //#[inline]
     pub fn rand_index(&mut self, size: u64) -> usize {
-        let rand: u64 = self.rand_u64() % SquaresRNG::U64_REMAINDER;
-        (rand % size) as usize
+        if size == 0 {
+            return 0;
+        }
+        self.rand_below(size) as usize
+    }
+
+    /// Returns a random u64 in 0..bound without modulo bias, by rerolling outputs that fall in the uneven remainder.
+    /// Bounds above 2^32 use two outputs per roll. bound must not be 0.
+    fn rand_below(&mut self, bound: u64) -> u64 {
+        if bound <= 1 << 32 {
+            let threshold: u64 = (1 << 32) % bound;
+            loop {
+                let rand: u64 = self.step();
+                if rand >= threshold {
+                    return rand % bound;
+                }
+            }
+        }
+
+        // 2^64 % bound, the number of values that would make the low indices more likely
+        let threshold: u64 = bound.wrapping_neg() % bound;
+        loop {
+            let rand: u64 = self.rand_u64();
+            if rand >= threshold {
+                return rand % bound;
+            }
+        }
     }
 
     /// Returns a random f32 between 0 and 1
@@ -77,9 +179,9 @@ impl SquaresRNG {
         // This is consistantly random enough without being too hard on performance.
 
         // Splitting rand
-        let rand: u64 = self.rand_u64() % SquaresRNG::U64_REMAINDER;
+        let rand: u64 = self.step() % SquaresRNG::U64_REMAINDER;
         // Getting place in split
-        return rand as f32 / SquaresRNG::U64_REMAINDER as f32;
+        rand as f32 / SquaresRNG::U64_REMAINDER as f32
     }
 
     /// Returns a random f32 between min and max.
@@ -88,10 +190,10 @@ impl SquaresRNG {
         min + (max - min) * self.randf32()
     }
 
-    /// Returns a random i32 between min and max.
+    /// Returns a random i32 between min and max (inclusive), with every value equally likely. min and max may be given in either order.
     //#[inline]
     pub fn rangei32(&mut self, min: i32, max: i32) -> i32 {
-        self.rangef32(min as f32, max as f32).round() as i32
+        self.range(min, max)
     }
 
     /// Returns a tuple set of two f32's
@@ -111,6 +213,40 @@ impl SquaresRNG {
     pub fn vec4f32(&mut self) -> (f32, f32, f32, f32) {
         (self.rangef32(-1.0, 1.0), self.rangef32(-1.0, 1.0), self.rangef32(-1.0, 1.0), self.rangef32(-1.0, 1.0))
     }
+
+    /// Returns a random direction as a point on the unit circle. Every angle is equally likely, unlike normalizing vec2f32.
+    //#[inline]
+    pub fn unit_vec2f32(&mut self) -> (f32, f32) {
+        let (sin, cos) = self.rangef32(0.0, core::f32::consts::TAU).sin_cos();
+        (cos, sin)
+    }
+
+    /// Returns a random direction as a point on the unit sphere. Every direction is equally likely, unlike normalizing vec3f32,
+    /// which clusters toward the corners of the cube. Picks a height evenly between -1 and 1 and an angle around it,
+    /// which covers the sphere evenly by area.
+    //#[inline]
+    pub fn unit_vec3f32(&mut self) -> (f32, f32, f32) {
+        let z: f32 = self.rangef32(-1.0, 1.0);
+        let azimuth: f32 = self.rangef32(0.0, core::f32::consts::TAU);
+        let radius: f32 = (1.0 - z * z).max(0.0).sqrt();
+        (radius * azimuth.cos(), radius * azimuth.sin(), z)
+    }
+
+    /// Returns a random point inside the unit circle. Every part of the disk is equally likely. The distance from the center is the
+    /// square root of a uniform value, since a plain uniform distance would bunch points up near the middle.
+    //#[inline]
+    pub fn in_unit_circle_f32(&mut self) -> (f32, f32) {
+        let radius: f32 = self.randf32().sqrt();
+        let (x, y) = self.unit_vec2f32();
+        (x * radius, y * radius)
+    }
+
+    /// Returns a random point inside a circle of the given radius centered on 0, 0. Every part of the disk is equally likely.
+    //#[inline]
+    pub fn in_circle_f32(&mut self, radius: f32) -> (f32, f32) {
+        let (x, y) = self.in_unit_circle_f32();
+        (x * radius, y * radius)
+    }
     
     /// Returns a random f64 between 0 and 1
     //#[inline]
@@ -121,9 +257,9 @@ impl SquaresRNG {
         // This is consistantly random enough without being too hard on performance.
 
         // Splitting rand
-        let rand: u64 = self.rand_u64() % SquaresRNG::U64_REMAINDER;
+        let rand: u64 = self.step() % SquaresRNG::U64_REMAINDER;
         // Getting place in split
-        return rand as f64 / SquaresRNG::U64_REMAINDER as f64;
+        rand as f64 / SquaresRNG::U64_REMAINDER as f64
     }
 
     /// Returns a random f64 between min and max.
@@ -132,10 +268,17 @@ impl SquaresRNG {
         min + (max - min) * self.randf64()
     }
 
-    /// Returns a random i64 between min and max.
+    /// Returns a random i64 between min and max (inclusive), with every value equally likely. min and max may be given in either order.
     //#[inline]
     pub fn rangei64(&mut self, min: i64, max: i64) -> i64 {
-        self.rangef64(min as f64, max as f64).round() as i64
+        self.range(min, max)
+    }
+
+    /// Returns a random integer of any standard integer type between min and max (inclusive), with every value equally likely.
+    /// min and max may be given in either order. rng.range::<u8>(0, 255) gives a random byte.
+    //#[inline]
+    pub fn range<T: SampleRange>(&mut self, min: T, max: T) -> T {
+        T::sample_range(self, min, max)
     }
 
     /// Returns a tuple set of two f64's
@@ -155,187 +298,5542 @@ impl SquaresRNG {
     pub fn vec4f64(&mut self) -> (f64, f64, f64, f64) {
         (self.rangef64(-1.0, 1.0), self.rangef64(-1.0, 1.0), self.rangef64(-1.0, 1.0), self.rangef64(-1.0, 1.0))
     }
-}
 
-#[test]
-pub fn test_f32_uniformality() {
-    
-    let mut rng: SquaresRNG = SquaresRNG::new_with_key(0, SquaresRNG::TEST_KEY);
+    /// Returns a random leading digit between 1 and 9 following Benford's law, where each digit d has a
+    /// probability of log10(1 + 1/d). Useful for generating plausible looking financial or statistical data.
+    //#[inline]
+    pub fn benford_digit(&mut self) -> u32 {
+        // P(D <= d) = log10(d + 1), so inverting the CDF gives floor(10^u) for a uniform u in [0, 1)
+        let digit: u32 = 10.0f64.powf(self.randf64()).floor() as u32;
+        digit.clamp(1, 9)
+    }
 
-    let count: u64 = SquaresRNG::TEST_COUNT;
-    let mut average: f32 = 0.0;
+    /// Returns a random i64 between min and max (inclusive) that is not in the excluded set, or None if every value is excluded.
+    /// Samples from the reduced count of allowed values rather than rerolling, so large exclusion sets stay fast.
+    //#[inline]
+    pub fn gen_range_excluding_i64(&mut self, min: i64, max: i64, excluded: &[i64]) -> Option<i64> {
+        if min > max {
+            return None;
+        }
 
-    for _ in 0..count {
-        average += rng.randf32();
+        let mut skipped: Vec<i64> = excluded.iter().copied().filter(|e| *e >= min && *e <= max).collect();
+        skipped.sort_unstable();
+        skipped.dedup();
+
+        let span: u128 = (max as i128 - min as i128 + 1) as u128;
+        let allowed: u128 = span - skipped.len() as u128;
+        if allowed == 0 {
+            return None;
+        }
+
+        // Pick the n-th allowed value, then step over each excluded value at or below it. With nothing excluded from the
+        // full i64 range there are 2^64 allowed values, which only fits in a u64 as the largest offset
+        let mut value: i128 = min as i128 + self.range::<u64>(0, (allowed - 1) as u64) as i128;
+        for skip in skipped {
+            if skip as i128 <= value {
+                value += 1;
+            } else {
+                break;
+            }
+        }
+
+        Some(value as i64)
     }
-    
-    let result: f32 = average / count as f32;
-    println!("\nRNG f32 Uniformality (Closer to 0.5): {}\n", result);
-    assert!(result < 0.51 && result > 0.49);
-}
 
-#[test]
-pub fn test_vec2f32_uniformality() {
-    let mut rng: SquaresRNG = SquaresRNG::new_with_key(0, SquaresRNG::TEST_KEY);
+    /// Returns a phrase of random words picked from the word list (repeats allowed), joined by spaces.
+    /// Pass the phrase to SquaresRNG::from_seed_phrase to get a shareable, reproducible generator.
+    //#[inline]
+    pub fn seed_phrase(&mut self, word_list: &[&str], words: usize) -> String {
+        if word_list.is_empty() {
+            return String::new();
+        }
 
-    let count: u64 = SquaresRNG::TEST_COUNT;
-    let mut average: (f32, f32) = (0.0, 0.0);
+        let mut picked: Vec<&str> = Vec::with_capacity(words);
+        for _ in 0..words {
+            picked.push(word_list[self.rand_index(word_list.len() as u64)]);
+        }
 
-    for _ in 0..count {
-        let rnd = rng.vec2f32();
-        average.0 += rnd.0;
-        average.1 += rnd.1;
+        picked.join(" ")
     }
 
-    average.0 /= count as f32;
-    average.1 /= count as f32;
-    
-    println!("\nRNG vec2f32 Uniformality (Closer to 0.0): x: {}, y: {}\n", average.0, average.1);
+    /// New random generator seeded from a phrase. The same phrase will always produce the same generator.
+    /// The phrase is hashed (FNV-1a) and the hash is mixed into a key with evenly distributed bits.
+    pub fn from_seed_phrase(phrase: &str) -> SquaresRNG {
+        let mut hash: u64 = 0xcbf29ce484222325;
+        for byte in phrase.bytes() {
+            hash ^= byte as u64;
+            hash = hash.wrapping_mul(0x100000001b3);
+        }
 
-    assert!(average.0 < 0.01 && average.0 > -0.01);
-    assert!(average.1 < 0.01 && average.1 > -0.01);
-}
+        SquaresRNG::new_with_key(0, SquaresRNG::derive_key(hash))
+    }
 
-#[test]
-pub fn test_vec3f32_uniformality() {
-    let mut rng: SquaresRNG = SquaresRNG::new_with_key(0, SquaresRNG::TEST_KEY);
+    /// Mixes a seed (SplitMix64) until it produces a key with roughly equal counts of 0 bits and 1 bits.
+    fn derive_key(seed: u64) -> u64 {
+        let mut state: u64 = seed;
+        loop {
+            state = state.wrapping_add(0x9e3779b97f4a7c15);
 
-    let count: u64 = SquaresRNG::TEST_COUNT;
-    let mut average: (f32, f32, f32) = (0.0, 0.0, 0.0);
+            let mut z: u64 = state;
+            z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+            let key: u64 = (z ^ (z >> 31)) | 1;
 
-    for _ in 0..count {
-        let rnd = rng.vec3f32();
-        average.0 += rnd.0;
-        average.1 += rnd.1;
-        average.2 += rnd.2;
+            if (28..=36).contains(&key.count_ones()) && SquaresRNG::validate_key(key) {
+                return key;
+            }
+        }
     }
 
-    average.0 /= count as f32;
-    average.1 /= count as f32;
-    average.2 /= count as f32;
-    
-    println!("\nRNG vec3f32 Uniformality (Closer to 0.0): x: {}, y: {}, z: {}\n", average.0, average.1, average.2);
-    
-    assert!(average.0 < 0.01 && average.0 > -0.01);
-    assert!(average.1 < 0.01 && average.1 > -0.01);
-    assert!(average.2 < 0.01 && average.2 > -0.01);
-}
+    /// Returns up to picks items drawn by weight without replacement, in the order they were drawn.
+    /// Each drawn item is removed from the pool and the remaining weights are renormalized.
+    /// Items with a weight that is not positive are never drawn. Returns an empty Vec if the slices differ in length.
+    //#[inline]
+    pub fn weighted_draft<'a, T>(&mut self, items: &'a [T], weights: &[f64], picks: usize) -> Vec<&'a T> {
+        let mut drafted: Vec<&'a T> = Vec::with_capacity(picks.min(items.len()));
+        if items.len() != weights.len() {
+            return drafted;
+        }
 
-#[test]
-pub fn test_vec4f32_uniformality() {
-    let mut rng: SquaresRNG = SquaresRNG::new_with_key(0, SquaresRNG::TEST_KEY);
+        let mut pool: Vec<(usize, f64)> = weights.iter().copied().enumerate().filter(|(_, w)| *w > 0.0 && w.is_finite()).collect();
 
-    let count: u64 = SquaresRNG::TEST_COUNT;
-    let mut average: (f32, f32, f32, f32) = (0.0, 0.0, 0.0, 0.0);
+        while drafted.len() < picks && !pool.is_empty() {
+            let total: f64 = pool.iter().map(|(_, w)| w).sum();
+            let mut target: f64 = self.randf64() * total;
 
-    for _ in 0..count {
-        let rnd = rng.vec4f32();
-        average.0 += rnd.0;
-        average.1 += rnd.1;
-        average.2 += rnd.2;
-        average.3 += rnd.3;
+            // Fall back to the last entry in case float error pushes the target past the total
+            let mut chosen: usize = pool.len() - 1;
+            for (slot, (_, weight)) in pool.iter().enumerate() {
+                if target < *weight {
+                    chosen = slot;
+                    break;
+                }
+                target -= weight;
+            }
+
+            let (index, _) = pool.remove(chosen);
+            drafted.push(&items[index]);
+        }
+
+        drafted
     }
 
-    average.0 /= count as f32;
-    average.1 /= count as f32;
-    average.2 /= count as f32;
-    average.3 /= count as f32;
-    
-    println!("\nRNG vec4f32 Uniformality (Closer to 0.0): w: {}, x: {}, y: {}, z: {}\n", average.3, average.0, average.1, average.2);
+    /// Returns a sub-pixel offset between -0.5 and 0.5 for temporal anti-aliasing, derived from the Halton (2, 3) sequence.
+    /// The same frame index always gets the same jitter. The sequence is rotated by an offset taken from the key
+    /// so different generators get different (but still well distributed) patterns. Does not advance the counter.
+    //#[inline]
+    pub fn taa_jitter(&mut self, frame_index: u64) -> (f32, f32) {
+        let mut rotation: SquaresRNG = SquaresRNG::new_with_key(0, self.key);
+        let offset: (f32, f32) = (rotation.randf32(), rotation.randf32());
 
-    assert!(average.0 < 0.01 && average.0 > -0.01);
-    assert!(average.1 < 0.01 && average.1 > -0.01);
-    assert!(average.2 < 0.01 && average.2 > -0.01);
-    assert!(average.3 < 0.01 && average.3 > -0.01);
-}
+        // Index 0 of the Halton sequence is always 0, so start from 1
+        let index: u64 = frame_index.wrapping_add(1);
+        let x: f32 = (SquaresRNG::halton(index, 2) + offset.0).fract();
+        let y: f32 = (SquaresRNG::halton(index, 3) + offset.1).fract();
 
-#[test]
-pub fn test_f64_uniformality() {
-    let mut rng: SquaresRNG = SquaresRNG::new_with_key(0, SquaresRNG::TEST_KEY);
+        (x - 0.5, y - 0.5)
+    }
 
-    let count: u64 = SquaresRNG::TEST_COUNT;
-    let mut average: f64 = 0.0;
+    /// Returns the element of the Halton sequence at index for the given base, between 0 and 1.
+    fn halton(mut index: u64, base: u64) -> f32 {
+        let mut fraction: f32 = 1.0;
+        let mut result: f32 = 0.0;
 
-    for _ in 0..count {
-        average += rng.randf64();
+        while index > 0 {
+            fraction /= base as f32;
+            result += fraction * (index % base) as f32;
+            index /= base;
+        }
+
+        result
     }
-    
-    let result: f64 = average / count as f64;
-    println!("\nRNG f64 Uniformality (Closer to 0.5): {}\n", result);
-    assert!(result < 0.51 && result > 0.49);
-}
 
-#[test]
-pub fn test_vec2f64_uniformality() {
-    let mut rng: SquaresRNG = SquaresRNG::new_with_key(0, SquaresRNG::TEST_KEY);
+    /// Rolls a d100 (1 to 100) and returns the value of the table entry whose inclusive (low, high) range contains the roll.
+    /// Returns None if the roll lands in a gap, or if the table is invalid (overlapping ranges, low > high, or outside 1 to 100).
+    //#[inline]
+    pub fn roll_d100_table<'a, T>(&mut self, table: &'a [(u32, u32, T)]) -> Option<&'a T> {
+        let mut covered: [bool; 101] = [false; 101];
+        for (low, high, _) in table {
+            if *low < 1 || *low > *high || *high > 100 {
+                return None;
+            }
+            for slot in covered.iter_mut().take(*high as usize + 1).skip(*low as usize) {
+                if *slot {
+                    return None;
+                }
+                *slot = true;
+            }
+        }
 
-    let count: u64 = SquaresRNG::TEST_COUNT;
-    let mut average: (f64, f64) = (0.0, 0.0);
+        let roll: u32 = self.rand_index(100) as u32 + 1;
+        table.iter().find(|(low, high, _)| roll >= *low && roll <= *high).map(|(_, _, value)| value)
+    }
 
-    for _ in 0..count {
-        let rnd = rng.vec2f64();
-        average.0 += rnd.0;
-        average.1 += rnd.1;
+    /// Returns count distinct indices in 0..range, sorted in ascending order. If count is larger than range, every index is returned.
+    /// Uses selection sampling, so the indices come out in order without a sort.
+    //#[inline]
+    pub fn sorted_indices(&mut self, count: usize, range: usize) -> Vec<usize> {
+        let mut needed: usize = count.min(range);
+        let mut indices: Vec<usize> = Vec::with_capacity(needed);
+
+        for index in 0..range {
+            if needed == 0 {
+                break;
+            }
+
+            // Each index is kept with probability needed / remaining, which keeps every subset equally likely
+            let remaining: usize = range - index;
+            if self.rand_index(remaining as u64) < needed {
+                indices.push(index);
+                needed -= 1;
+            }
+        }
+
+        indices
     }
 
-    average.0 /= count as f64;
-    average.1 /= count as f64;
-    
-    println!("\nRNG vec2f64 Uniformality (Closer to 0.0): x: {}, y: {}\n", average.0, average.1);
+    /// Returns an item picked with probability proportional to its weight, like a loot table. Returns None if the slices are empty,
+    /// differ in length, or the weights are invalid (negative, not finite, or all zero).
+    //#[inline]
+    pub fn choose_weighted<'a, T>(&mut self, items: &'a [T], weights: &[f64]) -> Option<&'a T> {
+        if items.len() != weights.len() {
+            return None;
+        }
+        self.weighted_index(weights).map(|index| &items[index])
+    }
 
-    assert!(average.0 < 0.01 && average.0 > -0.01);
-    assert!(average.1 < 0.01 && average.1 > -0.01);
-}
+    /// Returns an item picked with probability proportional to its weight, or default if the slices are empty,
+    /// differ in length, or the weights are invalid (negative, not finite, or all zero).
+    //#[inline]
+    pub fn choose_weighted_or<'a, T>(&mut self, items: &'a [T], weights: &[f64], default: &'a T) -> &'a T {
+        self.choose_weighted(items, weights).unwrap_or(default)
+    }
 
-#[test]
-pub fn test_vec3f64_uniformality() {
-    let mut rng: SquaresRNG = SquaresRNG::new_with_key(0, SquaresRNG::TEST_KEY);
+    /// Returns an index picked with probability proportional to its weight.
+    /// Returns None if the weights are empty, any weight is negative or not finite, or they sum to zero.
+    fn weighted_index(&mut self, weights: &[f64]) -> Option<usize> {
+        if weights.iter().any(|w| *w < 0.0 || !w.is_finite()) {
+            return None;
+        }
 
-    let count: u64 = SquaresRNG::TEST_COUNT;
-    let mut average: (f64, f64, f64) = (0.0, 0.0, 0.0);
+        let total: f64 = weights.iter().sum();
+        if total <= 0.0 || !total.is_finite() {
+            return None;
+        }
 
-    for _ in 0..count {
-        let rnd = rng.vec3f64();
-        average.0 += rnd.0;
-        average.1 += rnd.1;
-        average.2 += rnd.2;
+        let mut target: f64 = self.randf64() * total;
+        let mut chosen: usize = weights.iter().rposition(|w| *w > 0.0)?;
+        for (index, weight) in weights.iter().enumerate() {
+            if target < *weight {
+                chosen = index;
+                break;
+            }
+            target -= weight;
+        }
+
+        Some(chosen)
     }
 
-    average.0 /= count as f64;
-    average.1 /= count as f64;
-    average.2 /= count as f64;
-    
-    println!("\nRNG vec3f64 Uniformality (Closer to 0.0): x: {}, y: {}, z: {}\n", average.0, average.1, average.2);
-    
-    assert!(average.0 < 0.01 && average.0 > -0.01);
-    assert!(average.1 < 0.01 && average.1 > -0.01);
-    assert!(average.2 < 0.01 && average.2 > -0.01);
-}
+    /// Returns the base quaternion (x, y, z, w) rotated by a random angle up to max_angle_rad around a random axis.
+    /// Useful for small rotational perturbations like weapon recoil or aim wobble. The result is normalized.
+    //#[inline]
+    pub fn perturb_quaternion_f32(&mut self, base: (f32, f32, f32, f32), max_angle_rad: f32) -> (f32, f32, f32, f32) {
+        let axis: (f32, f32, f32) = self.unit_vec3f32();
 
-#[test]
-pub fn test_vec4f64_uniformality() {
-    let mut rng: SquaresRNG = SquaresRNG::new_with_key(0, SquaresRNG::TEST_KEY);
+        let angle: f32 = self.rangef32(0.0, max_angle_rad.abs());
+        let (sin, cos) = (angle * 0.5).sin_cos();
+        let delta: (f32, f32, f32, f32) = (axis.0 * sin, axis.1 * sin, axis.2 * sin, cos);
 
-    let count: u64 = SquaresRNG::TEST_COUNT;
-    let mut average: (f64, f64, f64, f64) = (0.0, 0.0, 0.0, 0.0);
+        // base * delta
+        let (bx, by, bz, bw) = base;
+        let (dx, dy, dz, dw) = delta;
+        let x: f32 = bw * dx + bx * dw + by * dz - bz * dy;
+        let y: f32 = bw * dy - bx * dz + by * dw + bz * dx;
+        let z: f32 = bw * dz + bx * dy - by * dx + bz * dw;
+        let w: f32 = bw * dw - bx * dx - by * dy - bz * dz;
 
-    for _ in 0..count {
-        let rnd = rng.vec4f64();
-        average.0 += rnd.0;
-        average.1 += rnd.1;
-        average.2 += rnd.2;
-        average.3 += rnd.3;
+        let length: f32 = (x * x + y * y + z * z + w * w).sqrt();
+        (x / length, y / length, z / length, w / length)
     }
 
-    average.0 /= count as f64;
-    average.1 /= count as f64;
-    average.2 /= count as f64;
-    average.3 /= count as f64;
-    
-    println!("\nRNG vec4f64 Uniformality (Closer to 0.0): w: {}, x: {}, y: {}, z: {}\n", average.3, average.0, average.1, average.2);
+    /// Returns a random value from a distribution described by a sampled inverse CDF. A uniform draw is located in the quantiles
+    /// and the matching values are linearly interpolated. Quantiles must be sorted ascending and the same length as values,
+    /// otherwise NaN is returned. Draws outside the quantile range are clamped to the first or last value.
+    //#[inline]
+    pub fn sample_inverse_cdf(&mut self, quantiles: &[f64], values: &[f64]) -> f64 {
+        if quantiles.is_empty() || quantiles.len() != values.len() || quantiles.windows(2).any(|pair| pair[0] > pair[1]) {
+            return f64::NAN;
+        }
 
-    assert!(average.0 < 0.01 && average.0 > -0.01);
-    assert!(average.1 < 0.01 && average.1 > -0.01);
-    assert!(average.2 < 0.01 && average.2 > -0.01);
-    assert!(average.3 < 0.01 && average.3 > -0.01);
-}
\ No newline at end of file
+        let u: f64 = self.randf64();
+        let upper: usize = quantiles.partition_point(|q| *q <= u);
+
+        if upper == 0 {
+            return values[0];
+        }
+        if upper == quantiles.len() {
+            return values[values.len() - 1];
+        }
+
+        let (q0, q1) = (quantiles[upper - 1], quantiles[upper]);
+        let (v0, v1) = (values[upper - 1], values[upper]);
+        if q1 == q0 {
+            return v1;
+        }
+
+        v0 + (v1 - v0) * (u - q0) / (q1 - q0)
+    }
+
+    /// Returns a random rank between 1 and n following a Zipf distribution, where rank k has a probability proportional to 1 / k^exponent.
+    /// Uses rejection-inversion sampling so it runs in constant time even for very large n. Returns 0 if n is 0 or the exponent is negative.
+    //#[inline]
+    pub fn zipf(&mut self, n: u64, exponent: f64) -> u64 {
+        if n == 0 || exponent.is_nan() || exponent < 0.0 {
+            return 0;
+        }
+
+        // Rejection-inversion (Hörmann and Derflinger), using H(x), the integral of h(x) = 1 / x^exponent
+        let h = |x: f64| (-exponent * x.ln()).exp();
+        let h_integral = |x: f64| {
+            let log_x: f64 = x.ln();
+            SquaresRNG::expm1_over_x((1.0 - exponent) * log_x) * log_x
+        };
+        let h_integral_inverse = |x: f64| {
+            let t: f64 = (x * (1.0 - exponent)).max(-1.0);
+            (SquaresRNG::ln1p_over_x(t) * x).exp()
+        };
+
+        let h_integral_x1: f64 = h_integral(1.5) - 1.0;
+        let h_integral_n: f64 = h_integral(n as f64 + 0.5);
+        let s: f64 = 2.0 - h_integral_inverse(h_integral(2.5) - h(2.0));
+
+        loop {
+            let u: f64 = h_integral_n + self.randf64() * (h_integral_x1 - h_integral_n);
+            let x: f64 = h_integral_inverse(u);
+            let k: u64 = ((x + 0.5) as u64).clamp(1, n);
+
+            if k as f64 - x <= s || u >= h_integral(k as f64 + 0.5) - h(k as f64) {
+                return k;
+            }
+        }
+    }
+
+    /// Returns ln(1 + x) / x, staying accurate when x is close to 0.
+    fn ln1p_over_x(x: f64) -> f64 {
+        if x.abs() > 1e-8 {
+            x.ln_1p() / x
+        } else {
+            1.0 - x * (0.5 - x * (1.0 / 3.0 - 0.25 * x))
+        }
+    }
+
+    /// Returns (e^x - 1) / x, staying accurate when x is close to 0.
+    fn expm1_over_x(x: f64) -> f64 {
+        if x.abs() > 1e-8 {
+            x.exp_m1() / x
+        } else {
+            1.0 + x * 0.5 * (1.0 + x / 3.0 * (1.0 + 0.25 * x))
+        }
+    }
+
+    /// Generates samples numbers with the key (starting from counter 0) and reports how evenly the output bytes are distributed.
+    /// Useful for checking whether a self-picked key is any good before using it. Each sample contributes the 4 bytes of its 32-bit output.
+    pub fn equidistribution_report(key: u64, samples: u64) -> EquidistReport {
+        let mut rng: SquaresRNG = SquaresRNG::new_with_key(0, key);
+        let mut byte_counts: [u64; 256] = [0; 256];
+        let mut total: f64 = 0.0;
+
+        for _ in 0..samples {
+            let rand: u64 = rng.step();
+            for byte in (rand as u32).to_le_bytes() {
+                byte_counts[byte as usize] += 1;
+            }
+            total += rand as f64 / (1u64 << 32) as f64;
+        }
+
+        let bytes: f64 = (samples * 4) as f64;
+        let expected: f64 = 1.0 / 256.0;
+        let distance: f64 = if samples == 0 {
+            1.0
+        } else {
+            0.5 * byte_counts.iter().map(|count| (*count as f64 / bytes - expected).abs()).sum::<f64>()
+        };
+
+        EquidistReport {
+            byte_counts,
+            mean: if samples == 0 { 0.0 } else { total / samples as f64 },
+            uniformity: 1.0 - distance,
+        }
+    }
+
+    /// Returns count points evenly spread over the unit sphere using a Fibonacci lattice, spun by a random angle for variety.
+    /// Unlike picking random points, the points do not clump together. Good for star fields or ambient occlusion sample directions.
+    //#[inline]
+    pub fn fibonacci_sphere_f32(&mut self, count: usize) -> Vec<(f32, f32, f32)> {
+        let golden_angle: f32 = core::f32::consts::PI * (3.0 - 5.0f32.sqrt());
+        let offset: f32 = self.rangef32(0.0, core::f32::consts::TAU);
+
+        (0..count).map(|i| {
+            let z: f32 = 1.0 - (2 * i + 1) as f32 / count as f32;
+            let radius: f32 = (1.0 - z * z).max(0.0).sqrt();
+            let (sin, cos) = (i as f32 * golden_angle + offset).sin_cos();
+            (radius * cos, radius * sin, z)
+        }).collect()
+    }
+
+    /// Returns a random f64 from weighted value bands. Each band is (low, high, weight): a band is picked by weight,
+    /// then a value is picked uniformly between its low and high. Returns NaN if there are no bands or any weight isn't positive.
+    //#[inline]
+    pub fn sample_weighted_ranges(&mut self, ranges: &[(f64, f64, f64)]) -> f64 {
+        if ranges.iter().any(|(_, _, weight)| *weight <= 0.0 || weight.is_nan()) {
+            return f64::NAN;
+        }
+
+        let weights: Vec<f64> = ranges.iter().map(|(_, _, weight)| *weight).collect();
+        match self.weighted_index(&weights) {
+            Some(index) => self.rangef64(ranges[index].0, ranges[index].1),
+            None => f64::NAN,
+        }
+    }
+
+    /// Shuffles the slice in place using a frozen, versioned algorithm. A seed and version pair will always produce the same
+    /// order, even if other shuffling methods in this crate change later. Returns false and leaves the slice untouched if the
+    /// version is unknown, such as one saved by a newer build. See SquaresRNG::CURRENT_SHUFFLE_VERSION.
+    ///
+    /// Versions:
+    /// 1. Fisher-Yates from the last element down, swapping element i with j in 0..=i. j is taken from the 32-bit output
+    ///    of the generator, rerolling outputs at or above the largest multiple of (i + 1) so every j is equally likely.
+    //#[inline]
+    pub fn shuffle_versioned<T>(&mut self, slice: &mut [T], algo_version: u8) -> bool {
+        match algo_version {
+            1 => {
+                for i in (1..slice.len()).rev() {
+                    let bound: u64 = i as u64 + 1;
+                    let zone: u64 = (1u64 << 32) - ((1u64 << 32) % bound);
+                    let mut rand: u64 = self.step();
+                    while rand >= zone {
+                        rand = self.step();
+                    }
+                    slice.swap(i, (rand % bound) as usize);
+                }
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Returns true if at least one event happened during a frame of dt_secs, for events that happen on average rate_per_sec times
+    /// per second (a Poisson process). For example, a chest that spawns on average every 30 seconds has a rate of 1.0 / 30.0.
+    //#[inline]
+    pub fn poisson_event(&mut self, rate_per_sec: f64, dt_secs: f64) -> bool {
+        let chance: f64 = 1.0 - (-rate_per_sec * dt_secs).exp();
+        self.randf64() < chance
+    }
+
+    /// Returns up to count non-overlapping rectangles (x, y, width, height) placed randomly inside the region, with sides between
+    /// min_size and max_size. Rectangles that would overlap are rejected, and placement gives up after max_attempts tries in total.
+    /// Rectangles may share edges. Good for room placement in dungeon generators.
+    //#[inline]
+    pub fn pack_random_rects(&mut self, region_w: u32, region_h: u32, count: usize, min_size: u32, max_size: u32, max_attempts: u32) -> Vec<(u32, u32, u32, u32)> {
+        let mut rects: Vec<(u32, u32, u32, u32)> = Vec::with_capacity(count);
+        let min_size: u32 = min_size.max(1);
+        if min_size > max_size || min_size > region_w || min_size > region_h {
+            return rects;
+        }
+
+        for _ in 0..max_attempts {
+            if rects.len() >= count {
+                break;
+            }
+
+            let w: u32 = min_size + self.rand_index((max_size.min(region_w) - min_size) as u64 + 1) as u32;
+            let h: u32 = min_size + self.rand_index((max_size.min(region_h) - min_size) as u64 + 1) as u32;
+            let x: u32 = self.rand_index((region_w - w) as u64 + 1) as u32;
+            let y: u32 = self.rand_index((region_h - h) as u64 + 1) as u32;
+
+            let overlaps: bool = rects.iter().any(|(ox, oy, ow, oh)| x < ox + ow && *ox < x + w && y < oy + oh && *oy < y + h);
+            if !overlaps {
+                rects.push((x, y, w, h));
+            }
+        }
+
+        rects
+    }
+
+    /// Returns a random element where later elements are more likely, weighting the element at index i by decay^(len - 1 - i).
+    /// A decay of 1.0 is a uniform pick, and smaller decays favor the most recent (last) elements more strongly.
+    /// Returns None if the slice is empty or decay is negative or not finite.
+    //#[inline]
+    pub fn choose_recency_weighted<'a, T>(&mut self, slice: &'a [T], decay: f64) -> Option<&'a T> {
+        if slice.is_empty() || decay < 0.0 || !decay.is_finite() {
+            return None;
+        }
+
+        let weights: Vec<f64> = (0..slice.len()).map(|i| decay.powi((slice.len() - 1 - i) as i32)).collect();
+        self.weighted_index(&weights).map(|index| &slice[index])
+    }
+
+    /// Returns a height by width grid (indexed as grid[y][x]) where each cell of the left half is filled with a chance of fill_prob,
+    /// then mirrored onto the right half. The result is always symmetric left to right. For odd widths the middle column is random.
+    //#[inline]
+    pub fn mirror_fill_grid(&mut self, width: usize, height: usize, fill_prob: f64) -> Vec<Vec<bool>> {
+        let half: usize = width.div_ceil(2);
+
+        (0..height).map(|_| {
+            let mut row: Vec<bool> = vec![false; width];
+            for x in 0..half {
+                let filled: bool = self.randf64() < fill_prob;
+                row[x] = filled;
+                row[width - 1 - x] = filled;
+            }
+            row
+        }).collect()
+    }
+
+    /// Returns an index picked with probability proportional to its weight, then sets that weight to 0 so it can't be picked again.
+    /// Calling this repeatedly on the same weights draws every index with a positive weight once, then returns None.
+    //#[inline]
+    pub fn choose_weighted_consume(&mut self, weights: &mut [f64]) -> Option<usize> {
+        let index: usize = self.weighted_index(weights)?;
+        weights[index] = 0.0;
+        Some(index)
+    }
+
+    /// Returns a random 64-bit seed that is not in existing, for making fresh procedural seeds that won't collide with old saves.
+    /// Rerolls on a collision. After 64 collisions in a row it steps up from the last roll to the next free seed instead.
+    //#[inline]
+    pub fn gen_unique_seed(&mut self, existing: &[u64]) -> u64 {
+        const MAX_RETRIES: u32 = 64;
+
+        let mut seed: u64 = 0;
+        for _ in 0..MAX_RETRIES {
+            // Each output is 32 bits wide, so two are combined for a full 64-bit seed
+            seed = self.rand_u64();
+            if !existing.contains(&seed) {
+                return seed;
+            }
+        }
+
+        while existing.contains(&seed) {
+            seed = seed.wrapping_add(1);
+        }
+        seed
+    }
+
+    /// Returns a height by width mask (indexed as mask[y][x]) with about density of its cells set, grouped into clusters.
+    /// Cells are either seeded at random or grown next to an existing set cell. clustering is the chance of growing instead of seeding,
+    /// so 0.0 scatters cells like independent coin flips and values close to 1.0 give a few large patches. Good for ore veins.
+    //#[inline]
+    pub fn clustered_mask(&mut self, width: usize, height: usize, density: f64, clustering: f64) -> Vec<Vec<bool>> {
+        let mut mask: Vec<Vec<bool>> = vec![vec![false; width]; height];
+        let target: usize = (density.clamp(0.0, 1.0) * (width * height) as f64).round() as usize;
+
+        let mut frontier: Vec<(usize, usize)> = Vec::new();
+        let mut filled: usize = 0;
+
+        while filled < target {
+            let cell: (usize, usize) = if frontier.is_empty() || self.randf64() >= clustering {
+                self.random_empty_cell(&mask)
+            } else {
+                frontier.swap_remove(self.rand_index(frontier.len() as u64))
+            };
+
+            let (x, y) = cell;
+            if mask[y][x] {
+                continue;
+            }
+            mask[y][x] = true;
+            filled += 1;
+
+            if x > 0 && !mask[y][x - 1] { frontier.push((x - 1, y)); }
+            if x + 1 < width && !mask[y][x + 1] { frontier.push((x + 1, y)); }
+            if y > 0 && !mask[y - 1][x] { frontier.push((x, y - 1)); }
+            if y + 1 < height && !mask[y + 1][x] { frontier.push((x, y + 1)); }
+        }
+
+        mask
+    }
+
+    /// Returns the (x, y) position of a random unset cell in the mask. The mask must have at least one unset cell.
+    fn random_empty_cell(&mut self, mask: &[Vec<bool>]) -> (usize, usize) {
+        let (width, height) = (mask[0].len(), mask.len());
+
+        for _ in 0..32 {
+            let x: usize = self.rand_index(width as u64);
+            let y: usize = self.rand_index(height as u64);
+            if !mask[y][x] {
+                return (x, y);
+            }
+        }
+
+        // Mostly full, pick from the remaining cells directly
+        let empty: Vec<(usize, usize)> = (0..height).flat_map(|y| (0..width).map(move |x| (x, y))).filter(|(x, y)| !mask[*y][*x]).collect();
+        empty[self.rand_index(empty.len() as u64)]
+    }
+
+    /// Returns the fittest of k randomly picked elements (picked with replacement). Larger k gives stronger selection pressure,
+    /// and k of 1 is a uniform pick. Returns None if the slice is empty or k is 0.
+    //#[inline]
+    pub fn tournament_select<'a, T, F: Fn(&T) -> f64>(&mut self, slice: &'a [T], k: usize, fitness_fn: F) -> Option<&'a T> {
+        if slice.is_empty() || k == 0 {
+            return None;
+        }
+
+        let mut best: &'a T = &slice[self.rand_index(slice.len() as u64)];
+        let mut best_fitness: f64 = fitness_fn(best);
+
+        for _ in 1..k {
+            let candidate: &'a T = &slice[self.rand_index(slice.len() as u64)];
+            let fitness: f64 = fitness_fn(candidate);
+            if fitness > best_fitness {
+                best = candidate;
+                best_fitness = fitness;
+            }
+        }
+
+        Some(best)
+    }
+
+    /// Runs the sampling closure samples times and returns how many results landed in each of buckets equal-width buckets,
+    /// spanning the smallest to the largest observed result. Handy for previewing custom distributions while tuning them.
+    //#[inline]
+    pub fn preview_distribution<F: FnMut(&mut SquaresRNG) -> i64>(&mut self, samples: u64, buckets: usize, mut f: F) -> Vec<u64> {
+        let mut counts: Vec<u64> = vec![0; buckets];
+        if buckets == 0 || samples == 0 {
+            return counts;
+        }
+
+        let results: Vec<i64> = (0..samples).map(|_| f(self)).collect();
+        let min: i128 = *results.iter().min().unwrap() as i128;
+        let max: i128 = *results.iter().max().unwrap() as i128;
+        let span: i128 = max - min + 1;
+
+        for result in results {
+            let bucket: i128 = (result as i128 - min) * buckets as i128 / span;
+            counts[bucket as usize] += 1;
+        }
+
+        counts
+    }
+
+    /// Returns a random value in 0..steps, representing one of steps equal rotations (multiply by 360 / steps for degrees).
+    /// Returns 0 if steps is 0.
+    //#[inline]
+    pub fn random_orientation_steps(&mut self, steps: u32) -> u32 {
+        if steps == 0 {
+            return 0;
+        }
+        self.rand_index(steps as u64) as u32
+    }
+
+    /// Returns a random value in 0..4, representing a rotation of 0, 90, 180, or 270 degrees.
+    //#[inline]
+    pub fn random_cardinal(&mut self) -> u32 {
+        self.random_orientation_steps(4)
+    }
+
+    /// Returns an antithetic pair (u, 1 - u) from a single random f64 between 0 and 1, advancing the counter only once.
+    /// Averaging a monotone function over antithetic pairs gives a lower variance estimate than two independent draws.
+    //#[inline]
+    pub fn antithetic_pair_f64(&mut self) -> (f64, f64) {
+        let u: f64 = self.randf64();
+        (u, 1.0 - u)
+    }
+
+    /// Returns count values of the form (start + i * stride) % modulus, where start and a stride coprime to modulus are random.
+    /// Every residue of modulus appears once before any repeats. Good for spreading objects across lanes or frames with a random phase.
+    /// Returns an empty Vec if modulus is 0.
+    //#[inline]
+    pub fn strided_sequence(&mut self, count: usize, modulus: usize) -> Vec<usize> {
+        if modulus == 0 {
+            return Vec::new();
+        }
+
+        let start: usize = self.rand_index(modulus as u64);
+        let mut stride: usize = 1;
+        if modulus > 2 {
+            loop {
+                stride = 1 + self.rand_index(modulus as u64 - 1);
+                if SquaresRNG::gcd(stride, modulus) == 1 {
+                    break;
+                }
+            }
+        }
+
+        let mut value: usize = start;
+        (0..count).map(|_| {
+            let current: usize = value;
+            value = ((value as u128 + stride as u128) % modulus as u128) as usize;
+            current
+        }).collect()
+    }
+
+    /// Returns the greatest common divisor of a and b.
+    fn gcd(mut a: usize, mut b: usize) -> usize {
+        while b != 0 {
+            (a, b) = (b, a % b);
+        }
+        a
+    }
+
+    /// Returns a random f64 between min and max following a power law, with a probability density proportional to x^-exponent.
+    /// Steeper exponents make smaller values more common. Requires 0 < min < max, otherwise NaN is returned.
+    //#[inline]
+    pub fn bounded_power_law(&mut self, min: f64, max: f64, exponent: f64) -> f64 {
+        if !(min > 0.0 && min < max) {
+            return f64::NAN;
+        }
+
+        let u: f64 = self.randf64();
+        let a: f64 = 1.0 - exponent;
+
+        // The CDF uses x^a, except for an exponent of 1 where it becomes logarithmic
+        let x: f64 = if a.abs() < 1e-12 {
+            min * (max / min).powf(u)
+        } else {
+            let low: f64 = min.powf(a);
+            (low + u * (max.powf(a) - low)).powf(1.0 / a)
+        };
+
+        x.clamp(min, max)
+    }
+
+    /// Returns a random unit direction blended toward downhill (the negative of the gradient) by bias.
+    /// A bias of 0.0 is a fully random direction and 1.0 is straight downhill. A zero gradient gives a fully random direction.
+    //#[inline]
+    pub fn gradient_biased_direction(&mut self, gradient: (f32, f32), bias: f32) -> (f32, f32) {
+        let (sin, cos) = self.rangef32(0.0, core::f32::consts::TAU).sin_cos();
+        let random: (f32, f32) = (cos, sin);
+
+        let slope: f32 = (gradient.0 * gradient.0 + gradient.1 * gradient.1).sqrt();
+        if slope <= f32::EPSILON {
+            return random;
+        }
+
+        let bias: f32 = bias.clamp(0.0, 1.0);
+        let downhill: (f32, f32) = (-gradient.0 / slope, -gradient.1 / slope);
+        let blended: (f32, f32) = (random.0 * (1.0 - bias) + downhill.0 * bias, random.1 * (1.0 - bias) + downhill.1 * bias);
+
+        // Only happens when the random direction points straight uphill with a bias of 0.5
+        let length: f32 = (blended.0 * blended.0 + blended.1 * blended.1).sqrt();
+        if length <= f32::EPSILON {
+            return downhill;
+        }
+
+        (blended.0 / length, blended.1 / length)
+    }
+
+    /// Returns corridors between rooms as pairs of indices into room_centers. The room centers are triangulated (Delaunay),
+    /// a minimum spanning tree is taken so every room is reachable, then 15% of the leftover triangulation edges are added back
+    /// at random to make some loops. Each pair is ordered with the smaller index first.
+    //#[inline]
+    pub fn room_corridors(&mut self, room_centers: &[(f32, f32)]) -> Vec<(usize, usize)> {
+        let count: usize = room_centers.len();
+        if count < 2 {
+            return Vec::new();
+        }
+
+        let points: Vec<(f64, f64)> = room_centers.iter().map(|(x, y)| (*x as f64, *y as f64)).collect();
+        let length = |(a, b): (usize, usize)| (points[a].0 - points[b].0).hypot(points[a].1 - points[b].1);
+
+        let mut edges: Vec<(usize, usize)> = SquaresRNG::delaunay_edges(&points);
+        edges.sort_by(|a, b| length(*a).total_cmp(&length(*b)));
+
+        let mut in_tree: Vec<bool> = SquaresRNG::spanning_tree(count, &edges);
+        if in_tree.iter().filter(|t| **t).count() < count - 1 {
+            // Degenerate layouts (like every room on one line) can't be triangulated, so fall back to every pair
+            edges = (0..count).flat_map(|a| (a + 1..count).map(move |b| (a, b))).collect();
+            edges.sort_by(|a, b| length(*a).total_cmp(&length(*b)));
+            in_tree = SquaresRNG::spanning_tree(count, &edges);
+        }
+
+        let mut corridors: Vec<(usize, usize)> = edges.iter().zip(in_tree.iter()).filter(|(_, t)| **t).map(|(e, _)| *e).collect();
+        let leftover: Vec<(usize, usize)> = edges.iter().zip(in_tree.iter()).filter(|(_, t)| !**t).map(|(e, _)| *e).collect();
+
+        let loops: usize = (leftover.len() as f64 * SquaresRNG::CORRIDOR_LOOP_FRACTION).round() as usize;
+        for index in self.sorted_indices(loops, leftover.len()) {
+            corridors.push(leftover[index]);
+        }
+
+        for corridor in corridors.iter_mut() {
+            *corridor = (corridor.0.min(corridor.1), corridor.0.max(corridor.1));
+        }
+        corridors
+    }
+
+    /// Returns the unique edges of the Delaunay triangulation of the points, using the Bowyer-Watson algorithm.
+    fn delaunay_edges(points: &[(f64, f64)]) -> Vec<(usize, usize)> {
+        let count: usize = points.len();
+        let (mut min_x, mut min_y, mut max_x, mut max_y) = (f64::MAX, f64::MAX, f64::MIN, f64::MIN);
+        for (x, y) in points {
+            min_x = min_x.min(*x);
+            min_y = min_y.min(*y);
+            max_x = max_x.max(*x);
+            max_y = max_y.max(*y);
+        }
+
+        // A super triangle that contains every point, its vertices are removed at the end
+        let size: f64 = (max_x - min_x).max(max_y - min_y).max(1.0) * 20.0;
+        let (mid_x, mid_y) = ((min_x + max_x) * 0.5, (min_y + max_y) * 0.5);
+        let mut vertices: Vec<(f64, f64)> = points.to_vec();
+        vertices.push((mid_x - size, mid_y - size));
+        vertices.push((mid_x + size, mid_y - size));
+        vertices.push((mid_x, mid_y + size));
+
+        let mut triangles: Vec<[usize; 3]> = vec![[count, count + 1, count + 2]];
+
+        for point in 0..count {
+            let p: (f64, f64) = vertices[point];
+            let (bad, good): (Vec<[usize; 3]>, Vec<[usize; 3]>) = triangles.into_iter().partition(|t| {
+                let (a, b, c) = (vertices[t[0]], vertices[t[1]], vertices[t[2]]);
+                let (adx, ady) = (a.0 - p.0, a.1 - p.1);
+                let (bdx, bdy) = (b.0 - p.0, b.1 - p.1);
+                let (cdx, cdy) = (c.0 - p.0, c.1 - p.1);
+                let det: f64 = (adx * adx + ady * ady) * (bdx * cdy - cdx * bdy)
+                    - (bdx * bdx + bdy * bdy) * (adx * cdy - cdx * ady)
+                    + (cdx * cdx + cdy * cdy) * (adx * bdy - bdx * ady);
+                det > 0.0
+            });
+            triangles = good;
+
+            // The hole left by the bad triangles is bounded by the edges they don't share with each other
+            let mut boundary: Vec<(usize, usize)> = Vec::new();
+            for t in bad.iter() {
+                for (a, b) in [(t[0], t[1]), (t[1], t[2]), (t[2], t[0])] {
+                    let shared: bool = bad.iter().any(|o| o != t && o.contains(&a) && o.contains(&b));
+                    if !shared {
+                        boundary.push((a, b));
+                    }
+                }
+            }
+
+            // Bad triangles are counter clockwise, so their boundary edges keep the new triangles counter clockwise
+            for (a, b) in boundary {
+                triangles.push([a, b, point]);
+            }
+        }
+
+        let mut edges: Vec<(usize, usize)> = Vec::new();
+        for t in triangles.iter().filter(|t| t.iter().all(|v| *v < count)) {
+            for (a, b) in [(t[0], t[1]), (t[1], t[2]), (t[2], t[0])] {
+                edges.push((a.min(b), a.max(b)));
+            }
+        }
+        edges.sort_unstable();
+        edges.dedup();
+        edges
+    }
+
+    /// Returns which edges (sorted shortest first) are part of the minimum spanning tree, using Kruskal's algorithm.
+    fn spanning_tree(count: usize, edges: &[(usize, usize)]) -> Vec<bool> {
+        let mut parents: Vec<usize> = (0..count).collect();
+        let find = |parents: &mut Vec<usize>, mut node: usize| -> usize {
+            while parents[node] != node {
+                parents[node] = parents[parents[node]];
+                node = parents[node];
+            }
+            node
+        };
+
+        edges.iter().map(|(a, b)| {
+            let (root_a, root_b) = (find(&mut parents, *a), find(&mut parents, *b));
+            if root_a != root_b {
+                parents[root_a] = root_b;
+            }
+            root_a != root_b
+        }).collect()
+    }
+
+    /// Returns the index of a random valid action, picked with probability proportional to its logit among the valid actions.
+    /// Logits are used directly as weights, and invalid actions (a false in valid_mask) are never picked.
+    /// Returns None if the slices differ in length, a valid logit is negative, or no valid action has a positive logit.
+    //#[inline]
+    pub fn sample_masked_policy(&mut self, logits: &[f64], valid_mask: &[bool]) -> Option<usize> {
+        if logits.len() != valid_mask.len() {
+            return None;
+        }
+
+        let weights: Vec<f64> = logits.iter().zip(valid_mask).map(|(logit, valid)| if *valid { *logit } else { 0.0 }).collect();
+        self.weighted_index(&weights)
+    }
+
+    /// New random generator for a chunk of a streaming world. The same world seed and chunk coordinates always produce the same
+    /// generator no matter what order chunks are loaded in, so chunks regenerate identical content when streamed back in.
+    /// Each chunk gets its own key (mixed from the world seed and coordinates) and starts at counter 0.
+    pub fn chunk_rng(world_seed: u64, chunk_x: i32, chunk_z: i32) -> SquaresRNG {
+        let packed: u64 = ((chunk_x as u32 as u64) << 32) | chunk_z as u32 as u64;
+        let key: u64 = SquaresRNG::derive_key(SquaresRNG::derive_key(world_seed) ^ packed);
+        SquaresRNG::new_with_key(0, key)
+    }
+
+    /// Returns references to a random subset of the slice where each element is included with a chance of per_item_prob,
+    /// so the subset size follows a binomial distribution rather than being fixed. Elements keep their original order.
+    //#[inline]
+    pub fn random_sized_subset<'a, T>(&mut self, slice: &'a [T], per_item_prob: f64) -> Vec<&'a T> {
+        slice.iter().filter(|_| self.randf64() < per_item_prob).collect()
+    }
+
+    /// Returns steps colors starting from a random color, where each channel of each color drifts from the previous one
+    /// by a random amount of at most max_channel_delta (saturating at 0 and 255). Good for slow ambient lighting cycles.
+    //#[inline]
+    pub fn color_walk_rgb8(&mut self, steps: usize, max_channel_delta: u8) -> Vec<[u8; 3]> {
+        let mut colors: Vec<[u8; 3]> = Vec::with_capacity(steps);
+        if steps == 0 {
+            return colors;
+        }
+
+        let mut color: [u8; 3] = [0; 3];
+        for channel in color.iter_mut() {
+            *channel = self.rand_index(256) as u8;
+        }
+        colors.push(color);
+
+        let span: u64 = max_channel_delta as u64 * 2 + 1;
+        for _ in 1..steps {
+            for channel in color.iter_mut() {
+                let delta: i32 = self.rand_index(span) as i32 - max_channel_delta as i32;
+                *channel = (*channel as i32 + delta).clamp(0, 255) as u8;
+            }
+            colors.push(color);
+        }
+
+        colors
+    }
+
+    /// Rolls a number in 0..last threshold and returns the index of the first threshold that is greater than the roll.
+    /// For loot tables stored as cumulative breakpoints, like [10, 30, 100] for 10%, 20%, and 70% chances.
+    /// Returns None if the thresholds are empty or not strictly increasing.
+    //#[inline]
+    pub fn roll_cumulative(&mut self, thresholds: &[u32]) -> Option<usize> {
+        let last: u32 = *thresholds.last()?;
+        if last == 0 || thresholds.windows(2).any(|pair| pair[0] >= pair[1]) {
+            return None;
+        }
+
+        let roll: u32 = self.rand_index(last as u64) as u32;
+        Some(thresholds.partition_point(|threshold| *threshold <= roll))
+    }
+
+    /// Returns a height by width grid (indexed as grid[y][x]) of bytes drawn from a normal distribution with the given mean
+    /// and standard deviation, rounded and clamped to 0..=255. Good for quick grain or noise textures.
+    //#[inline]
+    pub fn gaussian_grid_u8(&mut self, width: usize, height: usize, mean: f32, stddev: f32) -> Vec<Vec<u8>> {
+        (0..height).map(|_| {
+            (0..width).map(|_| {
+                let value: f32 = mean + stddev * self.standard_normal() as f32;
+                value.round().clamp(0.0, 255.0) as u8
+            }).collect()
+        }).collect()
+    }
+
+    /// Returns a random f32 from a normal (Gaussian) distribution with the given mean and standard deviation.
+    /// Uses the Box-Muller transform and advances the counter by 2. The value only depends on the key and counter.
+    //#[inline]
+    pub fn randf32_normal(&mut self, mean: f32, std_dev: f32) -> f32 {
+        mean + std_dev * self.standard_normal() as f32
+    }
+
+    /// Returns a random f64 from a normal (Gaussian) distribution with the given mean and standard deviation.
+    /// Uses the Box-Muller transform and advances the counter by 2. The value only depends on the key and counter.
+    //#[inline]
+    pub fn randf64_normal(&mut self, mean: f64, std_dev: f64) -> f64 {
+        mean + std_dev * self.standard_normal()
+    }
+
+    /// Returns a random f64 from the standard normal distribution (mean 0, standard deviation 1), using the Box-Muller transform.
+    /// Box-Muller makes two values at a time, but only one is used. Keeping the other would need state beyond the key and counter,
+    /// which setting the counter directly would leave stale.
+    fn standard_normal(&mut self) -> f64 {
+        // Uniforms from the full 32-bit output. Reject 0 so the log is always finite
+        let scale: f64 = 1.0 / (1u64 << 32) as f64;
+        let mut u: u64 = self.step();
+        while u == 0 {
+            u = self.step();
+        }
+        let v: f64 = self.step() as f64 * scale;
+
+        let radius: f64 = (-2.0 * (u as f64 * scale).ln()).sqrt();
+        radius * (core::f64::consts::TAU * v).cos()
+    }
+
+    /// Returns a random point on the edges of a rectangle from (0, 0) to (width, height), uniform along the perimeter
+    /// so longer edges get proportionally more points. Good for spawning enemies along the screen edges.
+    //#[inline]
+    pub fn on_rect_perimeter_f32(&mut self, width: f32, height: f32) -> (f32, f32) {
+        let mut distance: f32 = self.rangef32(0.0, 2.0 * (width + height));
+
+        if distance < width {
+            return (distance, 0.0);
+        }
+        distance -= width;
+        if distance < height {
+            return (width, distance);
+        }
+        distance -= height;
+        if distance < width {
+            return (width - distance, height);
+        }
+        distance -= width;
+
+        (0.0, (height - distance).max(0.0))
+    }
+
+    /// Returns a random selection of items whose costs add up to at most budget. Affordable items are picked at random
+    /// (each item at most once) until none of the remaining items fit in what's left of the budget. Good for procedural loadouts.
+    //#[inline]
+    pub fn fill_budget<'a, T, F: Fn(&T) -> u32>(&mut self, items: &'a [T], budget: u32, cost_fn: F) -> Vec<&'a T> {
+        let mut remaining: u32 = budget;
+        let mut pool: Vec<(&'a T, u32)> = items.iter().map(|item| (item, cost_fn(item))).collect();
+        let mut picked: Vec<&'a T> = Vec::new();
+
+        loop {
+            pool.retain(|(_, cost)| *cost <= remaining);
+            if pool.is_empty() {
+                return picked;
+            }
+
+            let (item, cost) = pool.swap_remove(self.rand_index(pool.len() as u64));
+            remaining -= cost;
+            picked.push(item);
+        }
+    }
+
+    /// Returns a random i64 between min and max (inclusive) with a bell shaped distribution, made by averaging four uniform draws.
+    /// Values near the middle are the most common and the endpoints are rare. Good for stat rolls. min and max may be given in either order.
+    //#[inline]
+    pub fn discrete_normal(&mut self, min: i64, max: i64) -> i64 {
+        const DRAWS: u32 = 4;
+
+        let (low, high) = (min.min(max), min.max(max));
+        let buckets: f64 = (high as i128 - low as i128 + 1) as f64;
+
+        let average: f64 = (0..DRAWS).map(|_| self.randf64()).sum::<f64>() / DRAWS as f64;
+        let offset: i128 = ((average * buckets).floor() as i128).min(high as i128 - low as i128);
+
+        (low as i128 + offset) as i64
+    }
+
+    /// Returns the keys of the map in a random order that only depends on the generator, not on the HashMap's own iteration order.
+    /// The keys are sorted into a canonical order first, then shuffled. Needs the `std` feature.
+    #[cfg(feature = "std")]
+    //#[inline]
+    pub fn shuffled_keys<K: Clone + Ord, V>(&mut self, map: &std::collections::HashMap<K, V>) -> Vec<K> {
+        let mut keys: Vec<K> = map.keys().cloned().collect();
+        keys.sort_unstable();
+        self.shuffle(&mut keys);
+        keys
+    }
+
+    /// Shuffles the slice in place with an unbiased Fisher-Yates shuffle. Every order is equally likely.
+    //#[inline]
+    pub fn shuffle<T>(&mut self, slice: &mut [T]) {
+        for i in (1..slice.len()).rev() {
+            slice.swap(i, self.rand_index(i as u64 + 1));
+        }
+    }
+
+    /// Returns a uniformly random element with a chance of epsilon, and otherwise the element with the highest value
+    /// (the first one on ties). An epsilon of 0.0 always exploits and 1.0 always explores. Returns None if the slice is empty.
+    //#[inline]
+    pub fn epsilon_greedy<'a, T, F: Fn(&T) -> f64>(&mut self, slice: &'a [T], epsilon: f64, value_fn: F) -> Option<&'a T> {
+        if slice.is_empty() {
+            return None;
+        }
+
+        if self.randf64() < epsilon {
+            return Some(&slice[self.rand_index(slice.len() as u64)]);
+        }
+
+        let mut best: &'a T = &slice[0];
+        let mut best_value: f64 = value_fn(best);
+        for item in &slice[1..] {
+            let value: f64 = value_fn(item);
+            if value > best_value {
+                best = item;
+                best_value = value;
+            }
+        }
+
+        Some(best)
+    }
+
+    /// Returns a 1D fractal terrain profile made with midpoint displacement. Both endpoints are 0.0, and each midpoint is displaced
+    /// by a random amount that starts at up to 1.0 and is multiplied by roughness every subdivision, so higher roughness is more jagged.
+    /// The length is rounded up to a power of two plus one (at least 2).
+    //#[inline]
+    pub fn midpoint_displacement_1d(&mut self, length: usize, roughness: f32) -> Vec<f32> {
+        let segments: usize = length.saturating_sub(1).max(1).next_power_of_two();
+        let mut heights: Vec<f32> = vec![0.0; segments + 1];
+
+        let mut step: usize = segments;
+        let mut scale: f32 = 1.0;
+        while step > 1 {
+            let half: usize = step / 2;
+            for start in (0..segments).step_by(step) {
+                let average: f32 = (heights[start] + heights[start + step]) * 0.5;
+                heights[start + half] = average + self.rangef32(-scale, scale);
+            }
+            step = half;
+            scale *= roughness;
+        }
+
+        heights
+    }
+
+    /// Returns a recolored copy of the palette where each color's hue is shifted by a random amount of up to hue_shift_range degrees
+    /// in either direction, keeping its saturation and value. Good for procedural sprite variants. Grays stay unchanged.
+    //#[inline]
+    pub fn random_palette_map(&mut self, palette: &[[u8; 3]], hue_shift_range: f32) -> Vec<[u8; 3]> {
+        palette.iter().map(|color| {
+            let (hue, saturation, value) = SquaresRNG::rgb8_to_hsv(*color);
+            let shift: f32 = self.rangef32(-hue_shift_range.abs(), hue_shift_range.abs());
+            SquaresRNG::hsv_to_rgb8((hue + shift).rem_euclid(360.0), saturation, value)
+        }).collect()
+    }
+
+    /// Converts a color to hue (degrees, 0 to 360), saturation, and value (0 to 1).
+    fn rgb8_to_hsv(color: [u8; 3]) -> (f32, f32, f32) {
+        let [r, g, b] = color.map(|channel| channel as f32 / 255.0);
+        let max: f32 = r.max(g).max(b);
+        let delta: f32 = max - r.min(g).min(b);
+
+        let hue: f32 = if delta == 0.0 {
+            0.0
+        } else if max == r {
+            60.0 * ((g - b) / delta).rem_euclid(6.0)
+        } else if max == g {
+            60.0 * ((b - r) / delta + 2.0)
+        } else {
+            60.0 * ((r - g) / delta + 4.0)
+        };
+
+        let saturation: f32 = if max == 0.0 { 0.0 } else { delta / max };
+        (hue, saturation, max)
+    }
+
+    /// Converts hue (degrees, 0 to 360), saturation, and value (0 to 1) to a color.
+    fn hsv_to_rgb8(hue: f32, saturation: f32, value: f32) -> [u8; 3] {
+        let chroma: f32 = value * saturation;
+        let sector: f32 = (hue / 60.0).rem_euclid(6.0);
+        let x: f32 = chroma * (1.0 - (sector % 2.0 - 1.0).abs());
+
+        let (r, g, b) = match sector as u32 {
+            0 => (chroma, x, 0.0),
+            1 => (x, chroma, 0.0),
+            2 => (0.0, chroma, x),
+            3 => (0.0, x, chroma),
+            4 => (x, 0.0, chroma),
+            _ => (chroma, 0.0, x),
+        };
+
+        let m: f32 = value - chroma;
+        [r, g, b].map(|channel| ((channel + m) * 255.0).round().clamp(0.0, 255.0) as u8)
+    }
+
+    /// Returns a random 2D point from a normal distribution with the given mean and covariance matrix, for elliptical scatter.
+    /// Two standard normals are transformed by the Cholesky factor of the covariance.
+    /// The covariance must be symmetric and positive-definite, otherwise (NaN, NaN) is returned.
+    //#[inline]
+    pub fn gaussian2d_covariance(&mut self, mean: (f64, f64), cov: [[f64; 2]; 2]) -> (f64, f64) {
+        let determinant: f64 = cov[0][0] * cov[1][1] - cov[0][1] * cov[1][0];
+        if cov[0][1] != cov[1][0] || !(cov[0][0] > 0.0 && determinant > 0.0) {
+            return (f64::NAN, f64::NAN);
+        }
+
+        let l11: f64 = cov[0][0].sqrt();
+        let l21: f64 = cov[1][0] / l11;
+        let l22: f64 = (cov[1][1] - l21 * l21).sqrt();
+
+        let (z1, z2) = (self.standard_normal(), self.standard_normal());
+        (mean.0 + l11 * z1, mean.1 + l21 * z1 + l22 * z2)
+    }
+
+    /// Returns up to count non-overlapping (start, end) intervals inside [0, window], each between min_len and max_len long,
+    /// sorted by start. Intervals that would overlap are rejected, and placement gives up after max_attempts tries in total.
+    /// Intervals may touch. Good for scheduling events that shouldn't conflict.
+    //#[inline]
+    pub fn random_intervals(&mut self, window: f64, count: usize, min_len: f64, max_len: f64, max_attempts: u32) -> Vec<(f64, f64)> {
+        let mut intervals: Vec<(f64, f64)> = Vec::with_capacity(count);
+        if !(min_len >= 0.0 && min_len <= max_len && min_len <= window) {
+            return intervals;
+        }
+
+        for _ in 0..max_attempts {
+            if intervals.len() >= count {
+                break;
+            }
+
+            let length: f64 = self.rangef64(min_len, max_len.min(window));
+            let start: f64 = self.rangef64(0.0, window - length);
+            let end: f64 = start + length;
+
+            if !intervals.iter().any(|(s, e)| start < *e && *s < end) {
+                intervals.push((start, end));
+            }
+        }
+
+        intervals.sort_by(|a, b| a.0.total_cmp(&b.0));
+        intervals
+    }
+
+    /// Returns a value drawn uniformly between min and max along with its importance weight target(x) / proposal density.
+    /// proposal_max is the height of the uniform proposal density, which is 1 / (max - min) for a normalized proposal.
+    /// If it isn't positive, the normalized height is used. Averaging weight * f(x) over many samples estimates the integral of target * f.
+    //#[inline]
+    pub fn importance_sample<F: Fn(f64) -> f64>(&mut self, min: f64, max: f64, target: F, proposal_max: f64) -> (f64, f64) {
+        let density: f64 = if proposal_max > 0.0 && proposal_max.is_finite() { proposal_max } else { 1.0 / (max - min) };
+        let x: f64 = self.rangef64(min, max);
+        (x, target(x) / density)
+    }
+
+    /// Returns a random n by n Latin square, where every row and every column contains each value in 0..n exactly once.
+    /// Made by randomly permuting the rows, columns, and symbols of the cyclic square. Good for puzzle generation.
+    //#[inline]
+    pub fn random_latin_square(&mut self, n: usize) -> Vec<Vec<usize>> {
+        let mut rows: Vec<usize> = (0..n).collect();
+        let mut columns: Vec<usize> = (0..n).collect();
+        let mut symbols: Vec<usize> = (0..n).collect();
+        self.shuffle(&mut rows);
+        self.shuffle(&mut columns);
+        self.shuffle(&mut symbols);
+
+        rows.iter().map(|row| columns.iter().map(|column| symbols[(row + column) % n]).collect()).collect()
+    }
+
+    /// Picks a random index to leave out and returns it along with references to every other element, in order.
+    /// Useful for leave-one-out (jackknife) validation. An empty slice returns index 0 and no elements.
+    //#[inline]
+    pub fn jackknife_sample<'a, T>(&mut self, slice: &'a [T]) -> (usize, Vec<&'a T>) {
+        let left_out: usize = self.rand_index(slice.len() as u64);
+        let rest: Vec<&'a T> = slice.iter().enumerate().filter(|(i, _)| *i != left_out).map(|(_, item)| item).collect();
+        (left_out, rest)
+    }
+
+    /// Returns length random booleans where about target_true are true, with the true count always within target_true ± tolerance.
+    /// The count is drawn as if each entry were a coin flip with a chance of target_true / length, and redrawn if it lands outside
+    /// the tolerance. The true entries are then placed at random positions.
+    //#[inline]
+    pub fn constrained_bools(&mut self, length: usize, target_true: usize, tolerance: usize) -> Vec<bool> {
+        let target: usize = target_true.min(length);
+        let low: usize = target.saturating_sub(tolerance);
+        let high: usize = target.saturating_add(tolerance).min(length);
+        let chance: f64 = if length == 0 { 0.0 } else { target as f64 / length as f64 };
+
+        let mut count: usize = target;
+        for _ in 0..100 {
+            let drawn: usize = (0..length).filter(|_| self.randf64() < chance).count();
+            if (low..=high).contains(&drawn) {
+                count = drawn;
+                break;
+            }
+        }
+
+        let mut bools: Vec<bool> = vec![false; length];
+        for index in self.sorted_indices(count, length) {
+            bools[index] = true;
+        }
+        bools
+    }
+
+    /// Returns a random element picked with softmax probabilities of score / temperature. Low temperatures approach always picking
+    /// the highest score, and high temperatures approach a uniform pick. A temperature of 0 or less always picks the highest score.
+    /// Returns None if the slice is empty or a score is NaN.
+    //#[inline]
+    pub fn softmax_sample<'a, T, F: Fn(&T) -> f64>(&mut self, slice: &'a [T], temperature: f64, score_fn: F) -> Option<&'a T> {
+        let scores: Vec<f64> = slice.iter().map(score_fn).collect();
+        if scores.iter().any(|score| score.is_nan()) {
+            return None;
+        }
+
+        // Shift by the highest score so exp never overflows
+        let best: f64 = scores.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        let weights: Vec<f64> = if temperature > 0.0 {
+            scores.iter().map(|score| ((score - best) / temperature).exp()).collect()
+        } else {
+            scores.iter().map(|score| if *score == best { 1.0 } else { 0.0 }).collect()
+        };
+
+        self.weighted_index(&weights).map(|index| &slice[index])
+    }
+
+    /// Returns a random element of the slice, or None if it's empty. Every element is equally likely.
+    //#[inline]
+    pub fn choose<'a, T>(&mut self, slice: &'a [T]) -> Option<&'a T> {
+        if slice.is_empty() {
+            return None;
+        }
+        Some(&slice[self.rand_index(slice.len() as u64)])
+    }
+
+    /// Returns a mutable reference to a random element of the slice, or None if it's empty. Every element is equally likely.
+    //#[inline]
+    pub fn choose_mut<'a, T>(&mut self, slice: &'a mut [T]) -> Option<&'a mut T> {
+        if slice.is_empty() {
+            return None;
+        }
+        let index: usize = self.rand_index(slice.len() as u64);
+        Some(&mut slice[index])
+    }
+
+    /// Returns n references to elements picked independently and uniformly from the slice, so repeats are allowed.
+    /// Good for bootstrap resampling. Returns an empty Vec if the slice is empty.
+    //#[inline]
+    pub fn sample_with_replacement<'a, T>(&mut self, slice: &'a [T], n: usize) -> Vec<&'a T> {
+        if slice.is_empty() {
+            return Vec::new();
+        }
+        (0..n).map(|_| &slice[self.rand_index(slice.len() as u64)]).collect()
+    }
+
+    /// Walks the tree from the root (node 0), picking a child by weight at each node until reaching a leaf,
+    /// and returns the node indices along the way (starting with the root). A node whose children all have zero weight counts as a leaf.
+    //#[inline]
+    pub fn traverse_weighted_tree(&mut self, tree: &WeightedTree) -> Vec<usize> {
+        let mut path: Vec<usize> = Vec::new();
+        if tree.children.is_empty() {
+            return path;
+        }
+
+        let mut node: usize = 0;
+        path.push(node);
+
+        // Bounded by the node count in case the tree accidentally has a cycle
+        while path.len() <= tree.children.len() {
+            let edges: &[(usize, f64)] = &tree.children[node];
+            let weights: Vec<f64> = edges.iter().map(|(_, weight)| *weight).collect();
+            match self.weighted_index(&weights) {
+                Some(index) => {
+                    node = edges[index].0;
+                    path.push(node);
+                }
+                None => break,
+            }
+        }
+
+        path
+    }
+
+    /// Maps index in 0..n to a distinct value in 0..n, giving a random permutation of 0..n that never has to be stored.
+    /// Uses a 4 round Feistel network keyed from the generator's key and counter, cycle walking until the output lands in 0..n.
+    /// Mapping every index in 0..n visits every value exactly once. Does not advance the counter, so the same generator state
+    /// always gives the same permutation. Returns index unchanged if it is not below n.
+    //#[inline]
+    pub fn feistel_permute(&mut self, n: u64, index: u64) -> u64 {
+        if index >= n || n < 2 {
+            return index;
+        }
+
+        // Smallest even number of bits that covers n, split into two halves
+        let mut bits: u32 = 64 - (n - 1).leading_zeros();
+        bits += bits % 2;
+        let half: u32 = bits / 2;
+        let mask: u64 = (1u64 << half) - 1;
+
+        let mut round_keys: [u64; 4] = [0; 4];
+        let mut keys: SquaresRNG = SquaresRNG::new_with_key(self.counter, self.key);
+        for round_key in round_keys.iter_mut() {
+            *round_key = keys.rand_u64();
+        }
+
+        let mut value: u64 = index;
+        loop {
+            let (mut left, mut right) = (value >> half, value & mask);
+            for round_key in round_keys {
+                let mixed: u64 = SquaresRNG::derive_key(right ^ round_key) & mask;
+                (left, right) = (right, left ^ mixed);
+            }
+            value = (left << half) | right;
+
+            // The network permutes 0..2^bits, so stepping again from outside 0..n must eventually come back inside
+            if value < n {
+                return value;
+            }
+        }
+    }
+
+    /// Returns a random f64 from a triangular distribution between min and max, most likely to land near mode.
+    /// Returns NaN if mode is not between min and max.
+    //#[inline]
+    pub fn triangular_f64(&mut self, min: f64, max: f64, mode: f64) -> f64 {
+        if !(min <= mode && mode <= max) {
+            return f64::NAN;
+        }
+        if min == max {
+            return min;
+        }
+
+        let u: f64 = self.randf64();
+        let split: f64 = (mode - min) / (max - min);
+        if u < split {
+            min + (u * (max - min) * (mode - min)).sqrt()
+        } else {
+            max - ((1.0 - u) * (max - min) * (max - mode)).sqrt()
+        }
+    }
+
+    /// Returns a random f64 from an exponential distribution with the given rate, such as the time until the next event
+    /// that happens lambda times per second on average. Returns NaN if lambda is not positive.
+    //#[inline]
+    pub fn exponential_f64(&mut self, lambda: f64) -> f64 {
+        if lambda <= 0.0 || lambda.is_nan() {
+            return f64::NAN;
+        }
+
+        // 1 - u is never 0, so the log is always finite
+        -(1.0 - self.randf64()).ln() / lambda
+    }
+
+    /// Returns a random f64 from the given distribution, so the choice of distribution can come from data.
+    /// Gives the same value as calling the matching method directly with the same parameters.
+    //#[inline]
+    pub fn sample(&mut self, dist: &Distribution) -> f64 {
+        match *dist {
+            Distribution::Uniform { min, max } => self.rangef64(min, max),
+            Distribution::Normal { mean, stddev } => self.randf64_normal(mean, stddev),
+            Distribution::Triangular { min, max, mode } => self.triangular_f64(min, max, mode),
+            Distribution::Exponential { lambda } => self.exponential_f64(lambda),
+        }
+    }
+
+    /// Splits a width by height region into leaf rectangles (x, y, width, height) for binary space partition dungeons.
+    /// Each region is split in two along a random axis at a random position, favoring the longer side of stretched regions,
+    /// until it is too small to split without making a side shorter than min_leaf, or it is max_depth splits deep.
+    /// The leaves never overlap and always cover the whole region. Returns an empty Vec if the region is smaller than min_leaf.
+    //#[inline]
+    pub fn bsp_split(&mut self, width: u32, height: u32, min_leaf: u32, max_depth: u32) -> Vec<(u32, u32, u32, u32)> {
+        let min_leaf: u32 = min_leaf.max(1);
+        let mut leaves: Vec<(u32, u32, u32, u32)> = Vec::new();
+        if width < min_leaf || height < min_leaf {
+            return leaves;
+        }
+
+        let mut stack: Vec<((u32, u32, u32, u32), u32)> = vec![((0, 0, width, height), 0)];
+        while let Some(((x, y, w, h), depth)) = stack.pop() {
+            // Same as w >= min_leaf * 2, without overflowing for huge min_leaf
+            let split_x: bool = w / 2 >= min_leaf;
+            let split_y: bool = h / 2 >= min_leaf;
+            if depth >= max_depth || !(split_x || split_y) {
+                leaves.push((x, y, w, h));
+                continue;
+            }
+
+            let vertical: bool = if split_x && split_y {
+                if w as f32 > h as f32 * 1.25 {
+                    true
+                } else if h as f32 > w as f32 * 1.25 {
+                    false
+                } else {
+                    self.step() & 1 == 0
+                }
+            } else {
+                split_x
+            };
+
+            // Pushed second half first so leaves come out top left first
+            if vertical {
+                let cut: u32 = min_leaf + self.rand_index((w - min_leaf - min_leaf) as u64 + 1) as u32;
+                stack.push(((x + cut, y, w - cut, h), depth + 1));
+                stack.push(((x, y, cut, h), depth + 1));
+            } else {
+                let cut: u32 = min_leaf + self.rand_index((h - min_leaf - min_leaf) as u64 + 1) as u32;
+                stack.push(((x, y + cut, w, h - cut), depth + 1));
+                stack.push(((x, y, w, cut), depth + 1));
+            }
+        }
+
+        leaves
+    }
+
+    /// Moves the generator forward by n steps without generating them, as if rand_u32 was called n times. Wraps around at the end of the sequence.
+    //#[inline]
+    pub fn jump_ahead(&mut self, n: u64) {
+        self.set_position(self.counter.wrapping_add(n));
+    }
+
+    /// Moves the generator back by n steps, so the last n values will be generated again. Wraps around at the start of the sequence.
+    //#[inline]
+    pub fn jump_back(&mut self, n: u64) {
+        self.set_position(self.counter.wrapping_sub(n));
+    }
+
+    /// Returns the current position in the key's sequence. Save this to come back to the same point later with set_position.
+    //#[inline]
+    pub fn position(&self) -> u64 {
+        self.counter
+    }
+
+    /// Moves the generator to a position in the key's sequence, such as one saved from position. Same as setting counter.
+    //#[inline]
+    pub fn set_position(&mut self, counter: u64) {
+        self.counter = counter;
+    }
+
+    /// Returns a random angle in degrees for scattered sprites and decals: a random multiple of snap_degrees below 360,
+    /// plus up to jitter_degrees either way. The result is not wrapped, so it can fall slightly below 0 or past the last snap.
+    /// Returns NaN if snap_degrees is not positive or jitter_degrees is negative.
+    //#[inline]
+    pub fn jittered_snap_angle(&mut self, snap_degrees: f32, jitter_degrees: f32) -> f32 {
+        if snap_degrees <= 0.0 || !snap_degrees.is_finite() || jitter_degrees < 0.0 || jitter_degrees.is_nan() {
+            return f32::NAN;
+        }
+
+        let steps: u64 = ((360.0 / snap_degrees).ceil() as u64).max(1);
+        let snapped: f32 = self.rand_index(steps) as f32 * snap_degrees;
+        snapped + self.rangef32(-jitter_degrees, jitter_degrees)
+    }
+
+    /// Fills the slice with random f32's between 0 and 1, the same values as calling randf32 once per element.
+    //#[inline]
+    pub fn fill_f32_slice(&mut self, out: &mut [f32]) {
+        for value in out.iter_mut() {
+            *value = self.randf32();
+        }
+    }
+
+    /// Fills the slice with random f64's between 0 and 1, the same values as calling randf64 once per element.
+    //#[inline]
+    pub fn fill_f64_slice(&mut self, out: &mut [f64]) {
+        for value in out.iter_mut() {
+            *value = self.randf64();
+        }
+    }
+
+    /// Fills the slice with random f32's between min and max, the same values as calling rangef32 once per element.
+    //#[inline]
+    pub fn fill_range_f32_slice(&mut self, out: &mut [f32], min: f32, max: f32) {
+        for value in out.iter_mut() {
+            *value = self.rangef32(min, max);
+        }
+    }
+
+    /// Returns the color of a blackbody light at a random temperature between min_k and max_k Kelvin, for procedural lighting.
+    /// Low temperatures are red and orange (candles are around 1900K), 6600K is white, and higher temperatures turn blue.
+    /// Temperatures are clamped to 1000K to 40000K, where the approximation holds.
+    //#[inline]
+    pub fn random_color_temperature(&mut self, min_k: f32, max_k: f32) -> [u8; 3] {
+        let kelvin: f32 = self.rangef32(min_k, max_k);
+        SquaresRNG::kelvin_to_rgb8(kelvin)
+    }
+
+    /// Converts a color temperature in Kelvin to a color, using Tanner Helland's curve fit of the blackbody colors.
+    fn kelvin_to_rgb8(kelvin: f32) -> [u8; 3] {
+        let t: f32 = kelvin.clamp(1000.0, 40000.0) / 100.0;
+
+        let r: f32 = if t <= 66.0 { 255.0 } else { 329.69873 * (t - 60.0).powf(-0.13320476) };
+        let g: f32 = if t <= 66.0 { 99.4708 * t.ln() - 161.11957 } else { 288.12216 * (t - 60.0).powf(-0.075514846) };
+        let b: f32 = if t >= 66.0 {
+            255.0
+        } else if t <= 19.0 {
+            0.0
+        } else {
+            138.51773 * (t - 10.0).ln() - 305.0448
+        };
+
+        [r, g, b].map(|channel| channel.clamp(0.0, 255.0).round() as u8)
+    }
+
+    /// Returns an endless iterator of rand_u64 values, in the same order as calling rand_u64 repeatedly.
+    /// Bound it with take or similar, or it will never finish.
+    //#[inline]
+    pub fn iter_u64(&mut self) -> impl Iterator<Item = u64> + '_ {
+        core::iter::repeat_with(move || self.rand_u64())
+    }
+
+    /// Returns an endless iterator of randf32 values, in the same order as calling randf32 repeatedly.
+    /// Bound it with take or similar, or it will never finish.
+    //#[inline]
+    pub fn iter_f32(&mut self) -> impl Iterator<Item = f32> + '_ {
+        core::iter::repeat_with(move || self.randf32())
+    }
+
+    /// Returns total items drawn so each category gets its share, like spawning 50% ground, 30% air, and 20% boss enemies.
+    /// category_fn gives the category index of an item, and quotas[category] is that category's share (the shares don't have to add up to 1).
+    /// Shares are turned into whole counts that add up to total, rounding so the counts stay as close to the shares as possible.
+    /// Items are picked uniformly (with replacement) within each category and the result is shuffled.
+    /// Categories without any items, or past the end of quotas, get no share. Returns an empty Vec if no category can get a share,
+    /// or any quota is negative or not finite.
+    //#[inline]
+    pub fn quota_sample<'a, T, F: Fn(&T) -> usize>(&mut self, slice: &'a [T], category_fn: F, quotas: &[f64], total: usize) -> Vec<&'a T> {
+        let mut members: Vec<Vec<&'a T>> = vec![Vec::new(); quotas.len()];
+        for item in slice.iter() {
+            if let Some(category) = members.get_mut(category_fn(item)) {
+                category.push(item);
+            }
+        }
+
+        if quotas.iter().any(|quota| *quota < 0.0 || !quota.is_finite()) {
+            return Vec::new();
+        }
+        let shares: Vec<f64> = quotas.iter().zip(members.iter()).map(|(quota, items)| if items.is_empty() { 0.0 } else { *quota }).collect();
+        let sum: f64 = shares.iter().sum();
+        if sum <= 0.0 {
+            return Vec::new();
+        }
+
+        // Largest remainder rounding: floor every share, then hand the leftover counts to the largest fractions
+        let exact: Vec<f64> = shares.iter().map(|share| share / sum * total as f64).collect();
+        let mut counts: Vec<usize> = exact.iter().map(|count| count.floor() as usize).collect();
+        let mut by_fraction: Vec<usize> = (0..exact.len()).filter(|&i| shares[i] > 0.0).collect();
+        by_fraction.sort_by(|&a, &b| (exact[b] - exact[b].floor()).total_cmp(&(exact[a] - exact[a].floor())).then(a.cmp(&b)));
+        let leftover: usize = total - counts.iter().sum::<usize>();
+        for &i in by_fraction.iter().cycle().take(leftover) {
+            counts[i] += 1;
+        }
+
+        let mut picked: Vec<&'a T> = Vec::with_capacity(total);
+        for (items, count) in members.iter().zip(counts) {
+            for _ in 0..count {
+                picked.push(items[self.rand_index(items.len() as u64)]);
+            }
+        }
+
+        self.shuffle(&mut picked);
+        picked
+    }
+
+    /// Rolls dice written in standard notation, like "2d6" (two six sided dice), "3d8+2", "d20" (one die), or "4d6-1".
+    /// Each die is an unbiased roll from 1 to its number of sides, and the total includes the modifier.
+    /// Returns an error if the notation is malformed, asks for zero dice, zero sided dice, or more than SquaresRNG::MAX_DICE dice,
+    /// or the total doesn't fit in an i64.
+    //#[inline]
+    pub fn roll(&mut self, notation: &str) -> Result<i64, DiceError> {
+        let notation: &str = notation.trim();
+        let (count, rest) = notation.split_once(['d', 'D']).ok_or(DiceError::Malformed)?;
+        let (sides, modifier) = match rest.find(['+', '-']) {
+            Some(at) => rest.split_at(at),
+            None => (rest, ""),
+        };
+
+        let parse = |digits: &str| -> Result<u64, DiceError> {
+            if digits.is_empty() || !digits.bytes().all(|byte| byte.is_ascii_digit()) {
+                return Err(DiceError::Malformed);
+            }
+            digits.parse::<u64>().map_err(|_| DiceError::Overflow)
+        };
+
+        let count: u64 = if count.is_empty() { 1 } else { parse(count)? };
+        let sides: u64 = parse(sides)?;
+        let modifier: i64 = match modifier.split_at_checked(1) {
+            Some(("+", amount)) => i64::try_from(parse(amount)?).map_err(|_| DiceError::Overflow)?,
+            Some(("-", amount)) => -i64::try_from(parse(amount)?).map_err(|_| DiceError::Overflow)?,
+            _ => 0,
+        };
+
+        if count == 0 {
+            return Err(DiceError::NoDice);
+        }
+        if count > SquaresRNG::MAX_DICE {
+            return Err(DiceError::TooManyDice);
+        }
+        if sides == 0 {
+            return Err(DiceError::NoSides);
+        }
+        let sides: i64 = i64::try_from(sides).map_err(|_| DiceError::Overflow)?;
+
+        let mut total: i64 = modifier;
+        for _ in 0..count {
+            total = total.checked_add(self.rangei64(1, sides)).ok_or(DiceError::Overflow)?;
+        }
+        Ok(total)
+    }
+
+    /// Returns a starting counter for a new stream that shares the parent's key without ever overlapping its output.
+    /// The sequence is split into 2^32 windows of 2^32 values each, starting at parent_counter. The parent keeps window 0,
+    /// and each stream_id gets its own window, shuffled by the parent key so nearby ids aren't next to each other.
+    /// As long as every stream (and the parent) draws fewer than 2^32 values, none of them repeat each other's values.
+    /// There are 2^32 - 1 windows to give out, so stream ids repeat after that many.
+    pub fn decorrelated_counter(parent_key: u64, parent_counter: u64, stream_id: u64) -> u64 {
+        const WINDOWS: u64 = u32::MAX as u64;
+
+        let mut permuter: SquaresRNG = SquaresRNG::new_with_key(0, parent_key);
+        let window: u64 = 1 + permuter.feistel_permute(WINDOWS, stream_id % WINDOWS);
+        parent_counter.wrapping_add(window << 32)
+    }
+
+    /// Returns a rows by cols grid of tile indices (indexed as grid[y][x]) into tileset.tiles, placed at random so that every pair
+    /// of neighboring tiles has matching edge colors. Tiles are placed row by row, each picked from the tiles that match
+    /// the tiles above and to the left. If a placement gets stuck the layout starts over, and after 100 tries an empty Vec is returned.
+    /// A complete tileset (a tile for every north and west color pair) never gets stuck.
+    //#[inline]
+    pub fn wang_tile_layout(&mut self, tileset: &WangTileSet, cols: usize, rows: usize) -> Vec<Vec<usize>> {
+        const MAX_TRIES: u32 = 100;
+
+        'tries: for _ in 0..MAX_TRIES {
+            let mut grid: Vec<Vec<usize>> = Vec::with_capacity(rows);
+            for _ in 0..rows {
+                let above_colors: Vec<Option<u8>> = match grid.last() {
+                    Some(above) => above.iter().map(|&tile| Some(tileset.tiles[tile][WangTileSet::SOUTH])).collect(),
+                    None => vec![None; cols],
+                };
+
+                let mut row: Vec<usize> = Vec::with_capacity(cols);
+                for above in above_colors {
+                    let left: Option<u8> = row.last().map(|&tile| tileset.tiles[tile][WangTileSet::EAST]);
+
+                    let fits: Vec<usize> = (0..tileset.tiles.len()).filter(|&tile| {
+                        let edges: [u8; 4] = tileset.tiles[tile];
+                        above.is_none_or(|color| edges[WangTileSet::NORTH] == color) && left.is_none_or(|color| edges[WangTileSet::WEST] == color)
+                    }).collect();
+
+                    match self.choose(&fits) {
+                        Some(&tile) => row.push(tile),
+                        None => continue 'tries,
+                    }
+                }
+                grid.push(row);
+            }
+            return grid;
+        }
+
+        Vec::new()
+    }
+
+    /// Returns which of beats beats trigger an effect, for rhythm games and music synced effects. About density of the beats
+    /// trigger, and triggers are always at least min_gap beats apart (a min_gap of 2 means there's always a quiet beat in between).
+    /// The most a pattern can trigger is one beat in min_gap, so densities above 1 / min_gap get that instead.
+    //#[inline]
+    pub fn beat_triggers(&mut self, beats: usize, density: f64, min_gap: usize) -> Vec<bool> {
+        let gap: usize = min_gap.max(1);
+        let density: f64 = if density.is_nan() { 0.0 } else { density.clamp(0.0, 1.0) };
+
+        // Each trigger silences the next gap - 1 beats, so the chance per open beat is raised to make up for them.
+        // With chance p the long run density is p / (1 + p * (gap - 1)), solved here for p.
+        let blocked: f64 = density * (gap - 1) as f64;
+        let chance: f64 = if blocked >= 1.0 { 1.0 } else { (density / (1.0 - blocked)).min(1.0) };
+
+        let mut triggers: Vec<bool> = vec![false; beats];
+        let mut next_open: usize = 0;
+        for (beat, trigger) in triggers.iter_mut().enumerate() {
+            if beat >= next_open && self.randf64() < chance {
+                *trigger = true;
+                next_open = beat + gap;
+            }
+        }
+
+        triggers
+    }
+
+    /// Returns references to a random subset of distinct elements, whose size is drawn from size_dist and rounded to the nearest
+    /// whole number. Sizes are clamped between 0 and the slice length (NaN counts as 0). Elements keep their original order.
+    //#[inline]
+    pub fn sample_dist_sized<'a, T>(&mut self, slice: &'a [T], size_dist: &Distribution) -> Vec<&'a T> {
+        let size: f64 = self.sample(size_dist).round();
+        let size: usize = if size.is_nan() { 0 } else { size.clamp(0.0, slice.len() as f64) as usize };
+
+        self.sorted_indices(size, slice.len()).into_iter().map(|index| &slice[index]).collect()
+    }
+
+    /// New random generator seeded from the system clock, for when different runs just need to be different.
+    /// The counter comes from the current time in nanoseconds, and the key is mixed from it (or picked from the keys table
+    /// with the keys_table feature). Calls in the same process are also numbered into the counter, so two calls in a row get
+    /// different generators even if the clock hasn't moved.
+    ///
+    /// This is not cryptographically secure. Anyone who can guess roughly when the generator was made can recreate it.
+    #[cfg(feature = "std")]
+    pub fn from_entropy() -> SquaresRNG {
+        static CALLS: core::sync::atomic::AtomicU64 = core::sync::atomic::AtomicU64::new(0);
+
+        let nanos: u64 = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_nanos() as u64)
+            .unwrap_or(0);
+        let call: u64 = CALLS.fetch_add(1, core::sync::atomic::Ordering::Relaxed);
+        let counter: u64 = nanos ^ call.rotate_right(24);
+        let seed: u64 = SquaresRNG::derive_key(counter);
+
+        #[cfg(feature = "keys_table")]
+        return SquaresRNG::new(counter, seed);
+        #[cfg(not(feature = "keys_table"))]
+        return SquaresRNG::new_with_key(counter, seed);
+    }
+
+    /// Returns a random sequence of length states (each in 0..states) picked by weight, where no (previous, next) pair in
+    /// forbidden ever appears back to back, like keeping two hard enemies from spawning in a row. Each step is picked by weight
+    /// from the states allowed after the previous one. States that would leave the sequence with nowhere to go are only used
+    /// at the very end. Returns an empty Vec if weights doesn't have one weight per state, the weights are invalid,
+    /// or the constraints leave no way to make a sequence that long.
+    //#[inline]
+    pub fn constrained_sequence(&mut self, states: usize, length: usize, forbidden: &[(usize, usize)], weights: &[f64]) -> Vec<usize> {
+        let mut sequence: Vec<usize> = Vec::with_capacity(length);
+        if weights.len() != states || weights.iter().any(|weight| *weight < 0.0 || !weight.is_finite()) {
+            return sequence;
+        }
+
+        let mut allowed: Vec<Vec<bool>> = vec![vec![true; states]; states];
+        for &(from, to) in forbidden.iter().filter(|(from, to)| *from < states && *to < states) {
+            allowed[from][to] = false;
+        }
+
+        // Drop states that can't be followed by anything that can keep going, until only states that can continue forever are left
+        let mut alive: Vec<bool> = weights.iter().map(|weight| *weight > 0.0).collect();
+        loop {
+            let dead: Vec<usize> = (0..states).filter(|&from| alive[from] && !(0..states).any(|to| alive[to] && allowed[from][to])).collect();
+            if dead.is_empty() {
+                break;
+            }
+            dead.into_iter().for_each(|state| alive[state] = false);
+        }
+
+        let mut previous: Option<usize> = None;
+        for step in 0..length {
+            let last: bool = step + 1 == length;
+            let step_weights: Vec<f64> = (0..states).map(|state| {
+                let usable: bool = (last || alive[state]) && previous.is_none_or(|from| allowed[from][state]);
+                if usable { weights[state] } else { 0.0 }
+            }).collect();
+
+            match self.weighted_index(&step_weights) {
+                Some(state) => {
+                    sequence.push(state);
+                    previous = Some(state);
+                }
+                None => return Vec::new(),
+            }
+        }
+
+        sequence
+    }
+
+    /// Returns the cells visited by a random walk on a hex grid in axial (q, r) coordinates, starting with start and followed by
+    /// steps moves. Each move goes to one of the six neighboring hexes, picked uniformly. Coordinates wrap at the i32 limits.
+    //#[inline]
+    pub fn hex_random_walk(&mut self, start: (i32, i32), steps: usize) -> Vec<(i32, i32)> {
+        const NEIGHBORS: [(i32, i32); 6] = [(1, 0), (1, -1), (0, -1), (-1, 0), (-1, 1), (0, 1)];
+
+        let mut path: Vec<(i32, i32)> = Vec::with_capacity(steps + 1);
+        let mut cell: (i32, i32) = start;
+        path.push(cell);
+        for _ in 0..steps {
+            let (dq, dr) = NEIGHBORS[self.rand_index(6)];
+            cell = (cell.0.wrapping_add(dq), cell.1.wrapping_add(dr));
+            path.push(cell);
+        }
+
+        path
+    }
+
+    /// Returns a child generator for its own independent stream, such as one per worker thread. The child's key is mixed
+    /// from the parent's next two outputs and its counter starts at 0. Advances the parent by 2, so every fork gives a different child,
+    /// and the same parent state always forks the same children.
+    //#[inline]
+    pub fn fork(&mut self) -> SquaresRNG {
+        let seed: u64 = self.rand_u64();
+        SquaresRNG::new_with_key(0, SquaresRNG::derive_key(seed))
+    }
+
+    /// Generates a whole dungeon from a single seed, so it can be shared or saved as just the seed and spec.
+    /// Places up to spec.rooms non-overlapping rooms, connects them with corridors (see room_corridors), then puts loot
+    /// on each room cell with a chance of spec.loot_density. Room placement gets 50 tries per room, at most u32::MAX tries in total.
+    /// The same seed and spec always give the same dungeon.
+    pub fn generate_dungeon(seed: u64, spec: &DungeonSpec) -> Dungeon {
+        let mut rng: SquaresRNG = SquaresRNG::new_with_key(0, SquaresRNG::derive_key(seed));
+
+        // Room counts past u32::MAX still get the most attempts a u32 can hold rather than wrapping around
+        let attempts: u32 = u32::try_from(spec.rooms).unwrap_or(u32::MAX).saturating_mul(50);
+        let rooms: Vec<(u32, u32, u32, u32)> = rng.pack_random_rects(spec.width, spec.height, spec.rooms, spec.min_room, spec.max_room, attempts);
+
+        let centers: Vec<(f32, f32)> = rooms.iter().map(|&(x, y, w, h)| (x as f32 + w as f32 * 0.5, y as f32 + h as f32 * 0.5)).collect();
+        let corridors: Vec<(usize, usize)> = rng.room_corridors(&centers);
+
+        let mut loot: Vec<(u32, u32)> = Vec::new();
+        for &(x, y, w, h) in rooms.iter() {
+            for cy in y..y + h {
+                for cx in x..x + w {
+                    if rng.randf64() < spec.loot_density {
+                        loot.push((cx, cy));
+                    }
+                }
+            }
+        }
+
+        Dungeon { rooms, corridors, loot }
+    }
+
+    /// Returns a random color as (red, green, blue), with every channel independent and every value from 0 to 255 equally likely.
+    /// All three channels come from a single output, so this only advances the counter once.
+    //#[inline]
+    pub fn color_rgb8(&mut self) -> (u8, u8, u8) {
+        let bits: u64 = self.step();
+        (bits as u8, (bits >> 8) as u8, (bits >> 16) as u8)
+    }
+
+    /// Returns a random color as (hue, saturation, value), with a random hue in degrees (0 up to 360) and the given saturation
+    /// and value. Keeping saturation and value fixed gives colors that differ only by hue, which makes easily told apart palettes.
+    //#[inline]
+    pub fn color_hsv(&mut self, s: f32, v: f32) -> (f32, f32, f32) {
+        // Rounding can push 360 * 0.99999 up to exactly 360, so wrap it back around
+        let hue: f32 = self.rangef32(0.0, 360.0);
+        (if hue >= 360.0 { 0.0 } else { hue }, s, v)
+    }
+
+    /// Returns an array of N random f32's between 0 and 1, the same values as calling randf32 N times. No heap allocation.
+    //#[inline]
+    pub fn gen_array_f32<const N: usize>(&mut self) -> [f32; N] {
+        core::array::from_fn(|_| self.randf32())
+    }
+
+    /// Returns an array of N rand_u64 outputs, the same values as calling rand_u64 N times. No heap allocation.
+    //#[inline]
+    pub fn gen_array_u64<const N: usize>(&mut self) -> [u64; N] {
+        core::array::from_fn(|_| self.rand_u64())
+    }
+
+    /// Returns a random f32 between min and max from a triangular distribution, most likely near mode and less likely toward the ends.
+    /// Good for values that are usually average, like damage rolls. mode is clamped between min and max.
+    /// Returns NaN if min is greater than max.
+    //#[inline]
+    pub fn triangular_f32(&mut self, min: f32, max: f32, mode: f32) -> f32 {
+        if min > max || min.is_nan() || max.is_nan() {
+            return f32::NAN;
+        }
+        let mode: f32 = mode.clamp(min, max);
+        if min == max {
+            return min;
+        }
+
+        let u: f32 = self.randf32();
+        let split: f32 = (mode - min) / (max - min);
+        if u < split {
+            min + (u * (max - min) * (mode - min)).sqrt()
+        } else {
+            max - ((1.0 - u) * (max - min) * (max - mode)).sqrt()
+        }
+    }
+}
+
+/// Results of SquaresRNG::equidistribution_report.
+#[derive(Debug, Clone)]
+pub struct EquidistReport {
+    /// How many times each byte value appeared in the output.
+    pub byte_counts: [u64; 256],
+    /// Average output, scaled to be between 0 and 1. Closer to 0.5 is better.
+    pub mean: f64,
+    /// 1 minus the total variation distance between the byte frequencies and a perfectly even distribution. Closer to 1 is better.
+    pub uniformity: f64,
+}
+
+/// A random boolean that prefers to keep its current state, for AI states that shouldn't flicker.
+/// While off it turns on with a chance of on_chance each step, and while on it turns off with a chance of off_chance.
+/// On average it stays off for 1 / on_chance steps and on for 1 / off_chance steps.
+pub struct HysteresisBool {
+    pub on_chance: f64,
+    pub off_chance: f64,
+    pub state: bool,
+}
+
+impl HysteresisBool {
+    /// Starts in the off state.
+    pub fn new(on_chance: f64, off_chance: f64) -> HysteresisBool {
+        HysteresisBool { on_chance, off_chance, state: false }
+    }
+
+    /// Steps the state once and returns it.
+    //#[inline]
+    pub fn next(&mut self, rng: &mut SquaresRNG) -> bool {
+        let flip_chance: f64 = if self.state { self.off_chance } else { self.on_chance };
+        if rng.randf64() < flip_chance {
+            self.state = !self.state;
+        }
+        self.state
+    }
+}
+
+/// Cycles through every value in 0..range in a random order, reshuffling after each full cycle, like a music player's shuffle-all mode.
+/// Every value appears exactly once per cycle before any value repeats.
+pub struct GlobalShuffleBag {
+    range: usize,
+    order: Vec<usize>,
+    position: usize,
+}
+
+impl GlobalShuffleBag {
+    /// Makes a bag that cycles through 0..range.
+    ///
+    /// # Panics
+    /// Panics if range is 0, since there is nothing to draw.
+    pub fn new(range: usize) -> GlobalShuffleBag {
+        assert!(range > 0, "GlobalShuffleBag range must be greater than 0");
+        GlobalShuffleBag { range, order: (0..range).collect(), position: range }
+    }
+
+    /// Returns the next value of the current cycle, starting a freshly shuffled cycle when the current one runs out.
+    //#[inline]
+    pub fn next(&mut self, rng: &mut SquaresRNG) -> usize {
+        if self.position >= self.order.len() {
+            rng.shuffle(&mut self.order);
+            self.position = 0;
+        }
+
+        self.position += 1;
+        self.order[self.position - 1]
+    }
+
+    /// Returns the size of the range the bag cycles through. Make a new bag to change it.
+    //#[inline]
+    pub fn range(&self) -> usize {
+        self.range
+    }
+}
+
+/// Picks random items while making sure no item is picked again until cooldown_secs have passed, like voice lines that shouldn't repeat.
+/// Items are tracked by their index, so pass the same slice (in the same order) to every call.
+pub struct TimedChooser {
+    pub cooldown_secs: f64,
+    last_used: Vec<Option<f64>>,
+}
+
+impl TimedChooser {
+    pub fn new(cooldown_secs: f64) -> TimedChooser {
+        TimedChooser { cooldown_secs, last_used: Vec::new() }
+    }
+
+    /// Returns a random item that hasn't been picked within cooldown_secs of now_secs, or None if every item is still cooling down.
+    //#[inline]
+    pub fn choose<'a, T>(&mut self, rng: &mut SquaresRNG, items: &'a [T], now_secs: f64) -> Option<&'a T> {
+        self.last_used.resize(items.len(), None);
+
+        let ready: Vec<usize> = (0..items.len()).filter(|i| match self.last_used[*i] {
+            Some(used) => now_secs - used >= self.cooldown_secs,
+            None => true,
+        }).collect();
+
+        if ready.is_empty() {
+            return None;
+        }
+
+        let index: usize = ready[rng.rand_index(ready.len() as u64)];
+        self.last_used[index] = Some(now_secs);
+        Some(&items[index])
+    }
+}
+
+/// A trigger that builds up charge and fires at random, for abilities that get more likely to go off the longer they charge.
+/// The chance to fire is charge * chance_per_charge (capped at 1.0), and the charge resets to 0 when it fires.
+pub struct ChargeTrigger {
+    pub chance_per_charge: f64,
+    pub charge: f64,
+}
+
+impl ChargeTrigger {
+    pub fn new(chance_per_charge: f64) -> ChargeTrigger {
+        ChargeTrigger { chance_per_charge, charge: 0.0 }
+    }
+
+    /// Adds to the accumulated charge.
+    //#[inline]
+    pub fn add_charge(&mut self, amount: f64) {
+        self.charge += amount;
+    }
+
+    /// Rolls against the current charge, returning true and resetting the charge if it fires.
+    //#[inline]
+    pub fn try_trigger(&mut self, rng: &mut SquaresRNG) -> bool {
+        let chance: f64 = (self.charge * self.chance_per_charge).clamp(0.0, 1.0);
+        if rng.randf64() < chance {
+            self.charge = 0.0;
+            return true;
+        }
+        false
+    }
+}
+
+/// Weighted choice that avoids long runs of the same result, for slot machine style rolls where long streaks feel unfair.
+/// The last result's weight is divided by 1 + penalty * streak, where streak is how many times in a row it has come up.
+pub struct StreakBreaker {
+    pub weights: Vec<f64>,
+    pub penalty: f64,
+    last: Option<usize>,
+    streak: usize,
+}
+
+impl StreakBreaker {
+    pub fn new(weights: &[f64], penalty: f64) -> StreakBreaker {
+        StreakBreaker { weights: weights.to_vec(), penalty, last: None, streak: 0 }
+    }
+
+    /// Returns an index picked by weight, with the last result made less likely the longer its streak.
+    /// Returns None if the weights are empty or invalid.
+    //#[inline]
+    pub fn choose(&mut self, rng: &mut SquaresRNG) -> Option<usize> {
+        let mut weights: Vec<f64> = self.weights.clone();
+        // weights is public, so the last pick may no longer be in range
+        if let Some(w) = self.last.and_then(|last| weights.get_mut(last)) {
+            *w /= 1.0 + self.penalty.max(0.0) * self.streak as f64;
+        }
+
+        let index: usize = rng.weighted_index(&weights)?;
+        if self.last == Some(index) {
+            self.streak += 1;
+        } else {
+            self.last = Some(index);
+            self.streak = 1;
+        }
+        Some(index)
+    }
+}
+
+/// Lets SquaresRNG be used with the rand ecosystem. Each generator output is 32 bits wide, so next_u32 advances the counter
+/// once (like randf32) and next_u64 advances it twice, putting the first output in the high bits.
+#[cfg(feature = "rand_core")]
+impl rand_core::RngCore for SquaresRNG {
+    fn next_u32(&mut self) -> u32 {
+        self.rand_u32()
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.rand_u64()
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        let mut chunks = dest.chunks_exact_mut(8);
+        for chunk in &mut chunks {
+            chunk.copy_from_slice(&self.next_u64().to_le_bytes());
+        }
+
+        let tail: &mut [u8] = chunks.into_remainder();
+        if !tail.is_empty() {
+            let bytes: [u8; 8] = self.next_u64().to_le_bytes();
+            tail.copy_from_slice(&bytes[..tail.len()]);
+        }
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand_core::Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+/// The seed is 16 bytes: the first 8 are the key and the last 8 are the counter (both little endian).
+/// seed_from_u64 mixes the number into a key with evenly distributed bits and starts at counter 0.
+#[cfg(feature = "rand_core")]
+impl rand_core::SeedableRng for SquaresRNG {
+    type Seed = [u8; 16];
+
+    fn from_seed(seed: Self::Seed) -> SquaresRNG {
+        let mut key: [u8; 8] = [0; 8];
+        let mut counter: [u8; 8] = [0; 8];
+        key.copy_from_slice(&seed[..8]);
+        counter.copy_from_slice(&seed[8..]);
+
+        SquaresRNG::new_with_key(u64::from_le_bytes(counter), u64::from_le_bytes(key))
+    }
+
+    fn seed_from_u64(state: u64) -> SquaresRNG {
+        SquaresRNG::new_with_key(0, SquaresRNG::derive_key(state))
+    }
+}
+
+/// A tree for SquaresRNG::traverse_weighted_tree, stored as each node's (child index, weight) edges. Node 0 is the root
+/// and nodes without children are leaves.
+pub struct WeightedTree {
+    pub children: Vec<Vec<(usize, f64)>>,
+}
+
+impl WeightedTree {
+    pub fn new(children: Vec<Vec<(usize, f64)>>) -> WeightedTree {
+        WeightedTree { children }
+    }
+}
+
+/// A distribution for SquaresRNG::sample, so designers can pick one in a config file.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Distribution {
+    /// Even chance of any value between min and max. See SquaresRNG::rangef64.
+    Uniform { min: f64, max: f64 },
+    /// Bell curve around mean. See SquaresRNG::randf64_normal.
+    Normal { mean: f64, stddev: f64 },
+    /// Values between min and max, most likely near mode. See SquaresRNG::triangular_f64.
+    Triangular { min: f64, max: f64, mode: f64 },
+    /// Waiting time between events that happen lambda times per unit on average. See SquaresRNG::exponential_f64.
+    Exponential { lambda: f64 },
+}
+
+/// Why SquaresRNG::new_with_key_checked rejected a key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyError {
+    /// The key has too many or too few 1 bits. Holds the count of 1 bits.
+    Unbalanced(u32),
+    /// The key is balanced, but its bits follow a simple pattern.
+    Patterned,
+}
+
+impl core::fmt::Display for KeyError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            KeyError::Unbalanced(ones) => write!(f, "key has {} one bits, expected between 24 and 44", ones),
+            KeyError::Patterned => write!(f, "key bits follow a simple pattern"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for KeyError {}
+
+/// Picks random items while making recently picked items less likely, for content feeds that should feel fresh.
+/// Each pick adds 1 to the item's fatigue, and items are weighted by 1 / (1 + fatigue). Call decay every tick to multiply
+/// all fatigue by decay_rate (between 0 and 1), so picked items slowly recover.
+pub struct FatigueChooser<T> {
+    pub items: Vec<T>,
+    pub decay_rate: f64,
+    fatigue: Vec<f64>,
+}
+
+impl<T> FatigueChooser<T> {
+    pub fn new(items: Vec<T>, decay_rate: f64) -> FatigueChooser<T> {
+        let fatigue: Vec<f64> = vec![0.0; items.len()];
+        FatigueChooser { items, decay_rate, fatigue }
+    }
+
+    /// Returns a random item, favoring the ones picked least recently and least often. Panics if there are no items.
+    //#[inline]
+    pub fn choose(&mut self, rng: &mut SquaresRNG) -> &T {
+        self.fatigue.resize(self.items.len(), 0.0);
+
+        let weights: Vec<f64> = self.fatigue.iter().map(|fatigue| 1.0 / (1.0 + fatigue)).collect();
+        let index: usize = rng.weighted_index(&weights).expect("FatigueChooser needs at least one item");
+        self.fatigue[index] += 1.0;
+        &self.items[index]
+    }
+
+    /// Lets every item recover a little by multiplying its fatigue by decay_rate.
+    //#[inline]
+    pub fn decay(&mut self) {
+        let rate: f64 = self.decay_rate.clamp(0.0, 1.0);
+        self.fatigue.iter_mut().for_each(|fatigue| *fatigue *= rate);
+    }
+
+    /// Returns how fatigued each item currently is, in the same order as items.
+    //#[inline]
+    pub fn fatigue(&self) -> &[f64] {
+        &self.fatigue
+    }
+}
+
+/// Why SquaresRNG::roll couldn't roll some dice notation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiceError {
+    /// The notation isn't in the form NdM, NdM+K, or NdM-K.
+    Malformed,
+    /// The notation asks for zero dice.
+    NoDice,
+    /// The notation asks for dice with zero sides.
+    NoSides,
+    /// The notation asks for more than SquaresRNG::MAX_DICE dice.
+    TooManyDice,
+    /// A number in the notation, or the total, is too big for an i64.
+    Overflow,
+}
+
+impl core::fmt::Display for DiceError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            DiceError::Malformed => write!(f, "dice notation should look like 2d6, d20, or 3d8+2"),
+            DiceError::NoDice => write!(f, "dice notation asks for zero dice"),
+            DiceError::NoSides => write!(f, "dice notation asks for dice with zero sides"),
+            DiceError::TooManyDice => write!(f, "dice notation asks for more than {} dice", SquaresRNG::MAX_DICE),
+            DiceError::Overflow => write!(f, "dice notation number or total is too big"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DiceError {}
+
+/// Tiles for SquaresRNG::wang_tile_layout, each described by the colors of its edges in [north, east, south, west] order.
+/// Neighboring tiles fit when the touching edges have the same color.
+pub struct WangTileSet {
+    pub tiles: Vec<[u8; 4]>,
+}
+
+impl WangTileSet {
+    pub const NORTH: usize = 0;
+    pub const EAST: usize = 1;
+    pub const SOUTH: usize = 2;
+    pub const WEST: usize = 3;
+
+    pub fn new(tiles: Vec<[u8; 4]>) -> WangTileSet {
+        WangTileSet { tiles }
+    }
+}
+
+/// A weighted distribution prepared for fast repeated draws, using Vose's alias method. Building it takes time proportional
+/// to the number of weights, but every sample after that takes the same small amount of time no matter how many weights there are.
+pub struct WeightedTable {
+    /// Chance of keeping each bucket's own index instead of its alias.
+    probability: Vec<f64>,
+    alias: Vec<usize>,
+}
+
+impl WeightedTable {
+    /// Builds the table for the given weights. Returns an error if there are no weights, any weight is negative or not finite,
+    /// or they add up to zero.
+    pub fn new(weights: &[f64]) -> Result<WeightedTable, WeightError> {
+        if weights.is_empty() {
+            return Err(WeightError::Empty);
+        }
+        if weights.iter().any(|weight| *weight < 0.0 || !weight.is_finite()) {
+            return Err(WeightError::InvalidWeight);
+        }
+        let total: f64 = weights.iter().sum();
+        if total <= 0.0 || !total.is_finite() {
+            return Err(WeightError::ZeroTotal);
+        }
+
+        let count: usize = weights.len();
+        let mut probability: Vec<f64> = weights.iter().map(|weight| weight * count as f64 / total).collect();
+        let mut alias: Vec<usize> = (0..count).collect();
+
+        let mut small: Vec<usize> = (0..count).filter(|&i| probability[i] < 1.0).collect();
+        let mut large: Vec<usize> = (0..count).filter(|&i| probability[i] >= 1.0).collect();
+        while let (Some(&less), Some(&more)) = (small.last(), large.last()) {
+            small.pop();
+            alias[less] = more;
+            probability[more] -= 1.0 - probability[less];
+            if probability[more] < 1.0 {
+                large.pop();
+                small.push(more);
+            }
+        }
+
+        // Whatever is left over only missed 1.0 by rounding error
+        for i in small.into_iter().chain(large) {
+            probability[i] = 1.0;
+        }
+
+        Ok(WeightedTable { probability, alias })
+    }
+
+    /// Returns an index picked with probability proportional to its weight. Uses a single rand_u32: the output is scaled
+    /// by the number of buckets, with the whole part picking the bucket and the fraction deciding between it and its alias.
+    //#[inline]
+    pub fn sample(&self, rng: &mut SquaresRNG) -> usize {
+        let scaled: u128 = rng.step() as u128 * self.probability.len() as u128;
+        let bucket: usize = (scaled >> 32) as usize;
+        let coin: f64 = (scaled as u32) as f64 / (1u64 << 32) as f64;
+
+        if coin < self.probability[bucket] {
+            bucket
+        } else {
+            self.alias[bucket]
+        }
+    }
+
+    /// Returns how many weights the table was built from.
+    //#[inline]
+    pub fn len(&self) -> usize {
+        self.probability.len()
+    }
+
+    /// Always false, since a table can't be built from no weights.
+    //#[inline]
+    pub fn is_empty(&self) -> bool {
+        self.probability.is_empty()
+    }
+}
+
+/// Why WeightedTable::new couldn't build a table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WeightError {
+    /// There were no weights.
+    Empty,
+    /// A weight was negative or not finite.
+    InvalidWeight,
+    /// The weights add up to zero.
+    ZeroTotal,
+}
+
+impl core::fmt::Display for WeightError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            WeightError::Empty => write!(f, "no weights were given"),
+            WeightError::InvalidWeight => write!(f, "a weight is negative or not finite"),
+            WeightError::ZeroTotal => write!(f, "the weights add up to zero"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for WeightError {}
+
+/// Counter 0 with a known good key (the first one in the keys table), for quick experiments.
+/// Always the same generator, not seeded from the time, so every run produces the same numbers.
+/// Pick your own key and counter with new_with_key once you need different sequences.
+impl Default for SquaresRNG {
+    fn default() -> SquaresRNG {
+        SquaresRNG::new_with_key(0, SquaresRNG::TEST_KEY)
+    }
+}
+
+/// What to build with SquaresRNG::generate_dungeon.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DungeonSpec {
+    pub width: u32,
+    pub height: u32,
+    /// Most rooms to place. Crowded dungeons can end up with fewer.
+    pub rooms: usize,
+    pub min_room: u32,
+    pub max_room: u32,
+    /// Chance of each room cell holding loot, between 0 and 1.
+    pub loot_density: f64,
+}
+
+impl DungeonSpec {
+    /// Rooms are 4 to 10 cells on a side. Change min_room and max_room afterwards for other sizes.
+    pub fn new(width: u32, height: u32, rooms: usize, loot_density: f64) -> DungeonSpec {
+        DungeonSpec { width, height, rooms, min_room: 4, max_room: 10, loot_density }
+    }
+}
+
+/// A dungeon made by SquaresRNG::generate_dungeon.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Dungeon {
+    /// Rooms as (x, y, width, height). They never overlap, but may share edges.
+    pub rooms: Vec<(u32, u32, u32, u32)>,
+    /// Pairs of indices into rooms that are connected. Every room can be reached from every other.
+    pub corridors: Vec<(usize, usize)>,
+    /// Cells (x, y) holding loot, always inside a room.
+    pub loot: Vec<(u32, u32)>,
+}
+
+/// Integer types that SquaresRNG::range can pick from. Implemented for every standard integer type up to 64 bits.
+pub trait SampleRange: Copy {
+    /// Returns a random value between min and max (inclusive), with every value equally likely. min and max may be given in either order.
+    fn sample_range(rng: &mut SquaresRNG, min: Self, max: Self) -> Self;
+}
+
+macro_rules! impl_sample_range {
+    ($($t:ty),*) => {$(
+        impl SampleRange for $t {
+            fn sample_range(rng: &mut SquaresRNG, min: $t, max: $t) -> $t {
+                let (low, high) = (min.min(max) as i128, min.max(max) as i128);
+                let span: u128 = (high - low) as u128;
+
+                // The full range of a 64-bit type has 2^64 values, one more than a u64 can count
+                if span == u64::MAX as u128 {
+                    return rng.rand_u64() as $t;
+                }
+                (low + rng.rand_below(span as u64 + 1) as i128) as $t
+            }
+        }
+    )*};
+}
+
+impl_sample_range!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+#[test]
+pub fn test_f32_uniformality() {
+    
+    let mut rng: SquaresRNG = SquaresRNG::new_with_key(0, SquaresRNG::TEST_KEY);
+
+    let count: u64 = SquaresRNG::TEST_COUNT;
+    let mut average: f32 = 0.0;
+
+    for _ in 0..count {
+        average += rng.randf32();
+    }
+    
+    let result: f32 = average / count as f32;
+    println!("\nRNG f32 Uniformality (Closer to 0.5): {}\n", result);
+    assert!(result < 0.51 && result > 0.49);
+}
+
+#[test]
+pub fn test_vec2f32_uniformality() {
+    let mut rng: SquaresRNG = SquaresRNG::new_with_key(0, SquaresRNG::TEST_KEY);
+
+    let count: u64 = SquaresRNG::TEST_COUNT;
+    let mut average: (f32, f32) = (0.0, 0.0);
+
+    for _ in 0..count {
+        let rnd = rng.vec2f32();
+        average.0 += rnd.0;
+        average.1 += rnd.1;
+    }
+
+    average.0 /= count as f32;
+    average.1 /= count as f32;
+    
+    println!("\nRNG vec2f32 Uniformality (Closer to 0.0): x: {}, y: {}\n", average.0, average.1);
+
+    assert!(average.0 < 0.01 && average.0 > -0.01);
+    assert!(average.1 < 0.01 && average.1 > -0.01);
+}
+
+#[test]
+pub fn test_vec3f32_uniformality() {
+    let mut rng: SquaresRNG = SquaresRNG::new_with_key(0, SquaresRNG::TEST_KEY);
+
+    let count: u64 = SquaresRNG::TEST_COUNT;
+    let mut average: (f32, f32, f32) = (0.0, 0.0, 0.0);
+
+    for _ in 0..count {
+        let rnd = rng.vec3f32();
+        average.0 += rnd.0;
+        average.1 += rnd.1;
+        average.2 += rnd.2;
+    }
+
+    average.0 /= count as f32;
+    average.1 /= count as f32;
+    average.2 /= count as f32;
+    
+    println!("\nRNG vec3f32 Uniformality (Closer to 0.0): x: {}, y: {}, z: {}\n", average.0, average.1, average.2);
+    
+    assert!(average.0 < 0.01 && average.0 > -0.01);
+    assert!(average.1 < 0.01 && average.1 > -0.01);
+    assert!(average.2 < 0.01 && average.2 > -0.01);
+}
+
+#[test]
+pub fn test_vec4f32_uniformality() {
+    let mut rng: SquaresRNG = SquaresRNG::new_with_key(0, SquaresRNG::TEST_KEY);
+
+    let count: u64 = SquaresRNG::TEST_COUNT;
+    let mut average: (f32, f32, f32, f32) = (0.0, 0.0, 0.0, 0.0);
+
+    for _ in 0..count {
+        let rnd = rng.vec4f32();
+        average.0 += rnd.0;
+        average.1 += rnd.1;
+        average.2 += rnd.2;
+        average.3 += rnd.3;
+    }
+
+    average.0 /= count as f32;
+    average.1 /= count as f32;
+    average.2 /= count as f32;
+    average.3 /= count as f32;
+    
+    println!("\nRNG vec4f32 Uniformality (Closer to 0.0): w: {}, x: {}, y: {}, z: {}\n", average.3, average.0, average.1, average.2);
+
+    assert!(average.0 < 0.01 && average.0 > -0.01);
+    assert!(average.1 < 0.01 && average.1 > -0.01);
+    assert!(average.2 < 0.01 && average.2 > -0.01);
+    assert!(average.3 < 0.01 && average.3 > -0.01);
+}
+
+#[test]
+pub fn test_f64_uniformality() {
+    let mut rng: SquaresRNG = SquaresRNG::new_with_key(0, SquaresRNG::TEST_KEY);
+
+    let count: u64 = SquaresRNG::TEST_COUNT;
+    let mut average: f64 = 0.0;
+
+    for _ in 0..count {
+        average += rng.randf64();
+    }
+    
+    let result: f64 = average / count as f64;
+    println!("\nRNG f64 Uniformality (Closer to 0.5): {}\n", result);
+    assert!(result < 0.51 && result > 0.49);
+}
+
+#[test]
+pub fn test_vec2f64_uniformality() {
+    let mut rng: SquaresRNG = SquaresRNG::new_with_key(0, SquaresRNG::TEST_KEY);
+
+    let count: u64 = SquaresRNG::TEST_COUNT;
+    let mut average: (f64, f64) = (0.0, 0.0);
+
+    for _ in 0..count {
+        let rnd = rng.vec2f64();
+        average.0 += rnd.0;
+        average.1 += rnd.1;
+    }
+
+    average.0 /= count as f64;
+    average.1 /= count as f64;
+    
+    println!("\nRNG vec2f64 Uniformality (Closer to 0.0): x: {}, y: {}\n", average.0, average.1);
+
+    assert!(average.0 < 0.01 && average.0 > -0.01);
+    assert!(average.1 < 0.01 && average.1 > -0.01);
+}
+
+#[test]
+pub fn test_vec3f64_uniformality() {
+    let mut rng: SquaresRNG = SquaresRNG::new_with_key(0, SquaresRNG::TEST_KEY);
+
+    let count: u64 = SquaresRNG::TEST_COUNT;
+    let mut average: (f64, f64, f64) = (0.0, 0.0, 0.0);
+
+    for _ in 0..count {
+        let rnd = rng.vec3f64();
+        average.0 += rnd.0;
+        average.1 += rnd.1;
+        average.2 += rnd.2;
+    }
+
+    average.0 /= count as f64;
+    average.1 /= count as f64;
+    average.2 /= count as f64;
+    
+    println!("\nRNG vec3f64 Uniformality (Closer to 0.0): x: {}, y: {}, z: {}\n", average.0, average.1, average.2);
+    
+    assert!(average.0 < 0.01 && average.0 > -0.01);
+    assert!(average.1 < 0.01 && average.1 > -0.01);
+    assert!(average.2 < 0.01 && average.2 > -0.01);
+}
+
+#[test]
+pub fn test_vec4f64_uniformality() {
+    let mut rng: SquaresRNG = SquaresRNG::new_with_key(0, SquaresRNG::TEST_KEY);
+
+    let count: u64 = SquaresRNG::TEST_COUNT;
+    let mut average: (f64, f64, f64, f64) = (0.0, 0.0, 0.0, 0.0);
+
+    for _ in 0..count {
+        let rnd = rng.vec4f64();
+        average.0 += rnd.0;
+        average.1 += rnd.1;
+        average.2 += rnd.2;
+        average.3 += rnd.3;
+    }
+
+    average.0 /= count as f64;
+    average.1 /= count as f64;
+    average.2 /= count as f64;
+    average.3 /= count as f64;
+    
+    println!("\nRNG vec4f64 Uniformality (Closer to 0.0): w: {}, x: {}, y: {}, z: {}\n", average.3, average.0, average.1, average.2);
+
+    assert!(average.0 < 0.01 && average.0 > -0.01);
+    assert!(average.1 < 0.01 && average.1 > -0.01);
+    assert!(average.2 < 0.01 && average.2 > -0.01);
+    assert!(average.3 < 0.01 && average.3 > -0.01);
+}
+
+#[test]
+pub fn test_benford_digit() {
+    let mut rng: SquaresRNG = SquaresRNG::new_with_key(0, SquaresRNG::TEST_KEY);
+
+    let count: u64 = 1_000_000;
+    let mut digits: [u64; 10] = [0; 10];
+
+    for _ in 0..count {
+        let digit: u32 = rng.benford_digit();
+        assert!((1..=9).contains(&digit));
+        digits[digit as usize] += 1;
+    }
+
+    let ones: f64 = digits[1] as f64 / count as f64;
+    let nines: f64 = digits[9] as f64 / count as f64;
+    println!("\nRNG Benford Digits (Closer to 0.301 and 0.046): 1: {}, 9: {}\n", ones, nines);
+
+    assert!(ones < 0.311 && ones > 0.291);
+    assert!(nines < 0.05);
+}
+
+#[test]
+pub fn test_gen_range_excluding_i64() {
+    let mut rng: SquaresRNG = SquaresRNG::new_with_key(0, SquaresRNG::TEST_KEY);
+
+    let count: u64 = 700_000;
+    let excluded: [i64; 4] = [2, 5, 7, 42];
+    let mut buckets: [u64; 10] = [0; 10];
+
+    for _ in 0..count {
+        let value: i64 = rng.gen_range_excluding_i64(0, 9, &excluded).unwrap();
+        assert!((0..=9).contains(&value));
+        assert!(!excluded.contains(&value));
+        buckets[value as usize] += 1;
+    }
+
+    println!("\nRNG Range Excluding Buckets (Closer to 100000 each): {:?}\n", buckets);
+
+    for (value, bucket) in buckets.iter().enumerate() {
+        if excluded.contains(&(value as i64)) {
+            assert_eq!(*bucket, 0);
+        } else {
+            assert!(*bucket < 102_000 && *bucket > 98_000);
+        }
+    }
+
+    assert_eq!(rng.gen_range_excluding_i64(3, 5, &[3, 4, 5]), None);
+    assert_eq!(rng.gen_range_excluding_i64(3, 5, &[3, 5]), Some(4));
+
+    // The full i64 range has one more value than a u64 can count
+    let mut top_bits: [u64; 4] = [0; 4];
+    for _ in 0..400_000 {
+        let value: i64 = rng.gen_range_excluding_i64(i64::MIN, i64::MAX, &[]).unwrap();
+        top_bits[((value as u64) >> 62) as usize] += 1;
+    }
+    assert!(top_bits.iter().all(|&count| (98_000..102_000).contains(&count)));
+    assert!(rng.gen_range_excluding_i64(i64::MIN, i64::MAX, &[0]).unwrap() != 0);
+}
+
+#[test]
+pub fn test_seed_phrase() {
+    let mut rng: SquaresRNG = SquaresRNG::new_with_key(0, SquaresRNG::TEST_KEY);
+
+    let word_list: [&str; 8] = ["apple", "brick", "cloud", "delta", "ember", "frost", "grove", "harbor"];
+    let phrase: String = rng.seed_phrase(&word_list, 4);
+    let other: String = rng.seed_phrase(&word_list, 4);
+
+    println!("\nRNG Seed Phrases: \"{}\", \"{}\"\n", phrase, other);
+
+    assert_eq!(phrase.split(' ').count(), 4);
+    assert!(phrase.split(' ').all(|word| word_list.contains(&word)));
+    assert_ne!(phrase, other);
+
+    let mut first: SquaresRNG = SquaresRNG::from_seed_phrase(&phrase);
+    let mut second: SquaresRNG = SquaresRNG::from_seed_phrase(&phrase);
+    let mut different: SquaresRNG = SquaresRNG::from_seed_phrase(&other);
+
+    assert_eq!(first.key, second.key);
+    assert_ne!(first.key, different.key);
+
+    let mut diverged: bool = false;
+    for _ in 0..100 {
+        let value: f64 = first.randf64();
+        assert_eq!(value, second.randf64());
+        diverged |= value != different.randf64();
+    }
+    assert!(diverged);
+}
+
+#[test]
+pub fn test_weighted_draft() {
+    let mut rng: SquaresRNG = SquaresRNG::new_with_key(0, SquaresRNG::TEST_KEY);
+
+    let items: [u32; 4] = [0, 1, 2, 3];
+    let weights: [f64; 4] = [1.0, 1.0, 1.0, 10.0];
+
+    let count: u64 = 100_000;
+    let mut first_picks: [u64; 4] = [0; 4];
+
+    for _ in 0..count {
+        let drafted: Vec<&u32> = rng.weighted_draft(&items, &weights, 3);
+        assert_eq!(drafted.len(), 3);
+        assert!(drafted[0] != drafted[1] && drafted[0] != drafted[2] && drafted[1] != drafted[2]);
+        first_picks[*drafted[0] as usize] += 1;
+    }
+
+    let favored: f64 = first_picks[3] as f64 / count as f64;
+    println!("\nRNG Weighted Draft First Picks (Closer to 0.769): {}\n", favored);
+
+    assert!(favored < 0.78 && favored > 0.76);
+    assert_eq!(rng.weighted_draft(&items, &weights, 10).len(), 4);
+    assert!(rng.weighted_draft(&items, &[1.0], 2).is_empty());
+}
+
+#[test]
+pub fn test_taa_jitter() {
+    let mut rng: SquaresRNG = SquaresRNG::new_with_key(0, SquaresRNG::TEST_KEY);
+
+    let frames: u64 = 64;
+    let mut columns: [u32; 8] = [0; 8];
+    let mut rows: [u32; 4] = [0; 4];
+
+    for frame in 0..frames {
+        let jitter: (f32, f32) = rng.taa_jitter(frame);
+        assert_eq!(jitter, rng.taa_jitter(frame));
+        assert!(jitter.0 >= -0.5 && jitter.0 <= 0.5);
+        assert!(jitter.1 >= -0.5 && jitter.1 <= 0.5);
+
+        columns[(((jitter.0 + 0.5) * 8.0) as usize).min(7)] += 1;
+        rows[(((jitter.1 + 0.5) * 4.0) as usize).min(3)] += 1;
+    }
+
+    println!("\nRNG TAA Jitter Distribution (Closer to 8 and 16): x: {:?}, y: {:?}\n", columns, rows);
+
+    assert!(columns.iter().all(|c| *c >= 7 && *c <= 9));
+    assert!(rows.iter().all(|r| *r >= 14 && *r <= 18));
+    assert_eq!(rng.counter, 0);
+}
+
+#[test]
+pub fn test_roll_d100_table() {
+    let mut rng: SquaresRNG = SquaresRNG::new_with_key(0, SquaresRNG::TEST_KEY);
+
+    let table: [(u32, u32, &str); 3] = [(1, 10, "goblin"), (11, 25, "orc"), (26, 75, "troll")];
+
+    let count: u64 = 1_000_000;
+    let mut goblins: u64 = 0;
+    let mut orcs: u64 = 0;
+    let mut trolls: u64 = 0;
+    let mut gaps: u64 = 0;
+
+    for _ in 0..count {
+        match rng.roll_d100_table(&table) {
+            Some(&"goblin") => goblins += 1,
+            Some(&"orc") => orcs += 1,
+            Some(&"troll") => trolls += 1,
+            Some(_) => unreachable!(),
+            None => gaps += 1,
+        }
+    }
+
+    let goblins: f64 = goblins as f64 / count as f64;
+    let orcs: f64 = orcs as f64 / count as f64;
+    let trolls: f64 = trolls as f64 / count as f64;
+    let gaps: f64 = gaps as f64 / count as f64;
+    println!("\nRNG d100 Table (Closer to 0.10, 0.15, 0.50, 0.25): {}, {}, {}, {}\n", goblins, orcs, trolls, gaps);
+
+    assert!(goblins < 0.105 && goblins > 0.095);
+    assert!(orcs < 0.155 && orcs > 0.145);
+    assert!(trolls < 0.505 && trolls > 0.495);
+    assert!(gaps < 0.255 && gaps > 0.245);
+
+    let overlapping: [(u32, u32, &str); 2] = [(1, 50, "goblin"), (50, 100, "orc")];
+    assert_eq!(rng.roll_d100_table(&overlapping), None);
+    let out_of_range: [(u32, u32, &str); 1] = [(0, 101, "goblin")];
+    assert_eq!(rng.roll_d100_table(&out_of_range), None);
+}
+
+#[test]
+pub fn test_sorted_indices() {
+    let mut rng: SquaresRNG = SquaresRNG::new_with_key(0, SquaresRNG::TEST_KEY);
+
+    let count: u64 = 100_000;
+    let mut hits: [u64; 20] = [0; 20];
+
+    for _ in 0..count {
+        let indices: Vec<usize> = rng.sorted_indices(5, 20);
+        assert_eq!(indices.len(), 5);
+        assert!(indices.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(indices.iter().all(|index| *index < 20));
+
+        for index in indices {
+            hits[index] += 1;
+        }
+    }
+
+    println!("\nRNG Sorted Indices Hits (Closer to 25000 each): {:?}\n", hits);
+
+    assert!(hits.iter().all(|h| *h < 26_000 && *h > 24_000));
+    assert_eq!(rng.sorted_indices(10, 4), vec![0, 1, 2, 3]);
+}
+
+#[test]
+pub fn test_choose_weighted_or() {
+    let mut rng: SquaresRNG = SquaresRNG::new_with_key(0, SquaresRNG::TEST_KEY);
+
+    let items: [&str; 3] = ["common", "rare", "never"];
+    let weights: [f64; 3] = [3.0, 1.0, 0.0];
+    let default: &str = "default";
+
+    let empty: [&str; 0] = [];
+    assert_eq!(*rng.choose_weighted_or(&empty, &[], &default), "default");
+    assert_eq!(*rng.choose_weighted_or(&items, &[1.0, 2.0], &default), "default");
+    assert_eq!(*rng.choose_weighted_or(&items, &[0.0, 0.0, 0.0], &default), "default");
+    assert_eq!(*rng.choose_weighted_or(&items, &[1.0, -1.0, 1.0], &default), "default");
+
+    let count: u64 = 1_000_000;
+    let mut common: u64 = 0;
+
+    for _ in 0..count {
+        match *rng.choose_weighted_or(&items, &weights, &default) {
+            "common" => common += 1,
+            "rare" => {}
+            other => panic!("unexpected choice {}", other),
+        }
+    }
+
+    let result: f64 = common as f64 / count as f64;
+    println!("\nRNG Weighted Or Default (Closer to 0.75): {}\n", result);
+    assert!(result < 0.755 && result > 0.745);
+}
+
+#[test]
+pub fn test_perturb_quaternion_f32() {
+    let mut rng: SquaresRNG = SquaresRNG::new_with_key(0, SquaresRNG::TEST_KEY);
+
+    let half: f32 = core::f32::consts::FRAC_1_SQRT_2;
+    let base: (f32, f32, f32, f32) = (0.0, half, 0.0, half);
+    let max_angle: f32 = 0.2;
+
+    let count: u64 = 100_000;
+    let mut largest: f32 = 0.0;
+
+    for _ in 0..count {
+        let q: (f32, f32, f32, f32) = rng.perturb_quaternion_f32(base, max_angle);
+
+        let length: f32 = (q.0 * q.0 + q.1 * q.1 + q.2 * q.2 + q.3 * q.3).sqrt();
+        assert!((length - 1.0).abs() < 1e-5);
+
+        let dot: f32 = (q.0 * base.0 + q.1 * base.1 + q.2 * base.2 + q.3 * base.3).abs().min(1.0);
+        let angle: f32 = 2.0 * dot.acos();
+        largest = largest.max(angle);
+    }
+
+    println!("\nRNG Quaternion Perturbation Largest Angle (At most 0.2): {}\n", largest);
+    assert!(largest <= max_angle + 1e-3);
+    assert!(largest > max_angle * 0.9);
+}
+
+#[test]
+pub fn test_sample_inverse_cdf() {
+    let mut rng: SquaresRNG = SquaresRNG::new_with_key(0, SquaresRNG::TEST_KEY);
+
+    let quantiles: [f64; 3] = [0.0, 0.5, 1.0];
+    let values: [f64; 3] = [10.0, 15.0, 20.0];
+
+    let count: u64 = 1_000_000;
+    let mut buckets: [u64; 10] = [0; 10];
+
+    for _ in 0..count {
+        let value: f64 = rng.sample_inverse_cdf(&quantiles, &values);
+        assert!((10.0..=20.0).contains(&value));
+        buckets[((value - 10.0) as usize).min(9)] += 1;
+    }
+
+    println!("\nRNG Inverse CDF Buckets (Closer to 100000 each): {:?}\n", buckets);
+
+    assert!(buckets.iter().all(|b| *b < 102_000 && *b > 98_000));
+    assert!(rng.sample_inverse_cdf(&[0.0, 1.0], &[1.0]).is_nan());
+    assert!(rng.sample_inverse_cdf(&[1.0, 0.0], &[1.0, 2.0]).is_nan());
+}
+
+#[test]
+pub fn test_zipf() {
+    let mut rng: SquaresRNG = SquaresRNG::new_with_key(0, SquaresRNG::TEST_KEY);
+
+    let count: u64 = 1_000_000;
+    let mut ranks: [u64; 11] = [0; 11];
+
+    for _ in 0..count {
+        let rank: u64 = rng.zipf(10, 1.0);
+        assert!((1..=10).contains(&rank));
+        ranks[rank as usize] += 1;
+    }
+
+    // With exponent 1 and n = 10, rank 1 has a probability of 1 / H(10)
+    let first: f64 = ranks[1] as f64 / count as f64;
+    let halved: f64 = ranks[1] as f64 / ranks[2] as f64;
+    let quartered: f64 = ranks[1] as f64 / ranks[4] as f64;
+    println!("\nRNG Zipf (Closer to 0.341, 2.0, 4.0): {}, {}, {}\n", first, halved, quartered);
+
+    assert!(ranks[1..].windows(2).all(|pair| pair[0] > pair[1]));
+    assert!(first < 0.346 && first > 0.336);
+    assert!(halved < 2.05 && halved > 1.95);
+    assert!(quartered < 4.1 && quartered > 3.9);
+
+    for _ in 0..1000 {
+        let rank: u64 = rng.zipf(u64::MAX / 2, 1.5);
+        assert!(rank >= 1);
+    }
+    assert_eq!(rng.zipf(0, 1.0), 0);
+    assert_eq!(rng.zipf(1, 2.0), 1);
+}
+
+#[test]
+pub fn test_equidistribution_report() {
+    let report: EquidistReport = SquaresRNG::equidistribution_report(SquaresRNG::TEST_KEY, 1_000_000);
+
+    let expected: f64 = 4_000_000.0 / 256.0;
+    let worst: f64 = report.byte_counts.iter().map(|count| (*count as f64 - expected).abs() / expected).fold(0.0, f64::max);
+    println!("\nRNG Equidistribution Report (Closer to 0.5 and 1.0): mean: {}, uniformity: {}, worst byte deviation: {}\n", report.mean, report.uniformity, worst);
+
+    assert_eq!(report.byte_counts.iter().sum::<u64>(), 4_000_000);
+    assert!(worst < 0.03);
+    assert!(report.mean < 0.501 && report.mean > 0.499);
+    assert!(report.uniformity > 0.99);
+}
+
+#[test]
+pub fn test_fibonacci_sphere_f32() {
+    let mut rng: SquaresRNG = SquaresRNG::new_with_key(0, SquaresRNG::TEST_KEY);
+
+    let count: usize = 500;
+    let lattice: Vec<(f32, f32, f32)> = rng.fibonacci_sphere_f32(count);
+    let random: Vec<(f32, f32, f32)> = (0..count).map(|_| {
+        let z: f32 = rng.rangef32(-1.0, 1.0);
+        let radius: f32 = (1.0 - z * z).sqrt();
+        let (sin, cos) = rng.rangef32(0.0, core::f32::consts::TAU).sin_cos();
+        (radius * cos, radius * sin, z)
+    }).collect();
+
+    assert_eq!(lattice.len(), count);
+    assert!(lattice.iter().all(|p| ((p.0 * p.0 + p.1 * p.1 + p.2 * p.2).sqrt() - 1.0).abs() < 1e-5));
+
+    // Coefficient of variation of each point's nearest neighbor distance, lower is more even
+    let spread = |points: &[(f32, f32, f32)]| -> f32 {
+        let nearest: Vec<f32> = points.iter().enumerate().map(|(i, a)| {
+            points.iter().enumerate().filter(|(j, _)| *j != i).map(|(_, b)| {
+                ((a.0 - b.0).powi(2) + (a.1 - b.1).powi(2) + (a.2 - b.2).powi(2)).sqrt()
+            }).fold(f32::MAX, f32::min)
+        }).collect();
+        let mean: f32 = nearest.iter().sum::<f32>() / nearest.len() as f32;
+        let variance: f32 = nearest.iter().map(|d| (d - mean).powi(2)).sum::<f32>() / nearest.len() as f32;
+        variance.sqrt() / mean
+    };
+
+    let lattice_spread: f32 = spread(&lattice);
+    let random_spread: f32 = spread(&random);
+    println!("\nRNG Fibonacci Sphere Nearest Neighbor Spread (Lattice lower): lattice: {}, random: {}\n", lattice_spread, random_spread);
+
+    assert!(lattice_spread < random_spread * 0.5);
+}
+
+#[test]
+pub fn test_sample_weighted_ranges() {
+    let mut rng: SquaresRNG = SquaresRNG::new_with_key(0, SquaresRNG::TEST_KEY);
+
+    let ranges: [(f64, f64, f64); 3] = [(0.0, 10.0, 6.0), (100.0, 200.0, 3.0), (1000.0, 5000.0, 1.0)];
+
+    let count: u64 = 1_000_000;
+    let mut bands: [u64; 3] = [0; 3];
+
+    for _ in 0..count {
+        let value: f64 = rng.sample_weighted_ranges(&ranges);
+        let band: usize = ranges.iter().position(|(low, high, _)| value >= *low && value <= *high).expect("value outside every band");
+        bands[band] += 1;
+    }
+
+    let bands: Vec<f64> = bands.iter().map(|b| *b as f64 / count as f64).collect();
+    println!("\nRNG Weighted Ranges (Closer to 0.6, 0.3, 0.1): {:?}\n", bands);
+
+    assert!(bands[0] < 0.605 && bands[0] > 0.595);
+    assert!(bands[1] < 0.305 && bands[1] > 0.295);
+    assert!(bands[2] < 0.105 && bands[2] > 0.095);
+    assert!(rng.sample_weighted_ranges(&[]).is_nan());
+    assert!(rng.sample_weighted_ranges(&[(0.0, 1.0, 1.0), (1.0, 2.0, 0.0)]).is_nan());
+}
+
+#[test]
+pub fn test_shuffle_versioned() {
+    let mut rng: SquaresRNG = SquaresRNG::new_with_key(0, SquaresRNG::TEST_KEY);
+
+    let mut values: [u32; 10] = [0, 1, 2, 3, 4, 5, 6, 7, 8, 9];
+    assert!(rng.shuffle_versioned(&mut values, 1));
+    println!("\nRNG Shuffle Version 1: {:?}\n", values);
+
+    // Pinned output, this must never change
+    assert_eq!(values, [1, 4, 5, 8, 3, 0, 2, 9, 6, 7]);
+
+    let mut again: [u32; 10] = [0, 1, 2, 3, 4, 5, 6, 7, 8, 9];
+    SquaresRNG::new_with_key(0, SquaresRNG::TEST_KEY).shuffle_versioned(&mut again, 1);
+    assert_eq!(values, again);
+
+    let mut empty: [u32; 0] = [];
+    assert!(rng.shuffle_versioned(&mut empty, 1));
+
+    // Unknown versions are rejected without touching the slice or the counter
+    let counter: u64 = rng.counter;
+    let mut unknown: [u32; 4] = [0, 1, 2, 3];
+    assert!(!rng.shuffle_versioned(&mut unknown, 0));
+    assert!(!rng.shuffle_versioned(&mut unknown, SquaresRNG::CURRENT_SHUFFLE_VERSION + 1));
+    assert_eq!(unknown, [0, 1, 2, 3]);
+    assert_eq!(rng.counter, counter);
+}
+
+#[test]
+pub fn test_poisson_event() {
+    let mut rng: SquaresRNG = SquaresRNG::new_with_key(0, SquaresRNG::TEST_KEY);
+
+    let rate: f64 = 1.0 / 30.0;
+    let dt: f64 = 1.0 / 60.0;
+    let frames: u64 = 6_000_000;
+
+    let mut events: u64 = 0;
+    for _ in 0..frames {
+        if rng.poisson_event(rate, dt) {
+            events += 1;
+        }
+    }
+
+    let expected: f64 = rate * dt * frames as f64;
+    println!("\nRNG Poisson Events (Closer to {}): {}\n", expected, events);
+
+    assert!((events as f64) < expected * 1.05 && (events as f64) > expected * 0.95);
+    assert!(!rng.poisson_event(0.0, dt));
+}
+
+#[test]
+pub fn test_pack_random_rects() {
+    let mut rng: SquaresRNG = SquaresRNG::new_with_key(0, SquaresRNG::TEST_KEY);
+
+    for _ in 0..100 {
+        let rects: Vec<(u32, u32, u32, u32)> = rng.pack_random_rects(64, 48, 12, 4, 12, 500);
+        assert!(!rects.is_empty() && rects.len() <= 12);
+
+        for (i, a) in rects.iter().enumerate() {
+            assert!(a.2 >= 4 && a.2 <= 12 && a.3 >= 4 && a.3 <= 12);
+            assert!(a.0 + a.2 <= 64 && a.1 + a.3 <= 48);
+
+            for b in rects.iter().skip(i + 1) {
+                let overlaps: bool = a.0 < b.0 + b.2 && b.0 < a.0 + a.2 && a.1 < b.1 + b.3 && b.1 < a.1 + a.3;
+                assert!(!overlaps, "{:?} overlaps {:?}", a, b);
+            }
+        }
+    }
+
+    assert!(rng.pack_random_rects(8, 8, 4, 10, 12, 100).is_empty());
+}
+
+#[test]
+pub fn test_choose_recency_weighted() {
+    let mut rng: SquaresRNG = SquaresRNG::new_with_key(0, SquaresRNG::TEST_KEY);
+
+    let items: [usize; 5] = [0, 1, 2, 3, 4];
+
+    let count: u64 = 500_000;
+    let mut recent: [u64; 5] = [0; 5];
+    let mut uniform: [u64; 5] = [0; 5];
+
+    for _ in 0..count {
+        recent[*rng.choose_recency_weighted(&items, 0.5).unwrap()] += 1;
+        uniform[*rng.choose_recency_weighted(&items, 1.0).unwrap()] += 1;
+    }
+
+    println!("\nRNG Recency Weighted (Increasing, then closer to 100000 each): {:?}, {:?}\n", recent, uniform);
+
+    assert!(recent.windows(2).all(|pair| pair[0] < pair[1]));
+    assert!(uniform.iter().all(|u| *u < 101_500 && *u > 98_500));
+
+    let empty: [usize; 0] = [];
+    assert_eq!(rng.choose_recency_weighted(&empty, 0.5), None);
+    assert_eq!(rng.choose_recency_weighted(&items, 0.0), Some(&4));
+}
+
+#[test]
+pub fn test_mirror_fill_grid() {
+    let mut rng: SquaresRNG = SquaresRNG::new_with_key(0, SquaresRNG::TEST_KEY);
+
+    for (width, height) in [(200, 150), (201, 150)] {
+        let grid: Vec<Vec<bool>> = rng.mirror_fill_grid(width, height, 0.3);
+        assert_eq!(grid.len(), height);
+
+        let mut filled: u64 = 0;
+        for row in grid.iter() {
+            assert_eq!(row.len(), width);
+            for x in 0..width {
+                assert_eq!(row[x], row[width - 1 - x]);
+            }
+            filled += row[..width.div_ceil(2)].iter().filter(|cell| **cell).count() as u64;
+        }
+
+        let result: f64 = filled as f64 / (width.div_ceil(2) * height) as f64;
+        println!("\nRNG Mirror Fill Grid Left Half (Closer to 0.3): {}\n", result);
+        assert!(result < 0.32 && result > 0.28);
+    }
+}
+
+#[test]
+pub fn test_choose_weighted_consume() {
+    let mut rng: SquaresRNG = SquaresRNG::new_with_key(0, SquaresRNG::TEST_KEY);
+
+    for _ in 0..1000 {
+        let mut weights: Vec<f64> = vec![5.0, 1.0, 0.0, 2.5, 0.5, 3.0];
+        let mut drawn: Vec<usize> = Vec::new();
+
+        while let Some(index) = rng.choose_weighted_consume(&mut weights) {
+            assert!(!drawn.contains(&index));
+            drawn.push(index);
+        }
+
+        drawn.sort_unstable();
+        assert_eq!(drawn, vec![0, 1, 3, 4, 5]);
+        assert!(weights.iter().all(|w| *w == 0.0));
+    }
+}
+
+#[test]
+pub fn test_gen_unique_seed() {
+    let mut rng: SquaresRNG = SquaresRNG::new_with_key(0, SquaresRNG::TEST_KEY);
+
+    // Existing seeds taken from the same generator, so the fresh draws really do collide
+    let mut existing: Vec<u64> = Vec::new();
+    for _ in 0..1000 {
+        existing.push(rng.rand_u64());
+    }
+
+    for _ in 0..10 {
+        rng.counter = 0;
+        for _ in 0..100 {
+            let seed: u64 = rng.gen_unique_seed(&existing);
+            assert!(!existing.contains(&seed));
+        }
+    }
+
+    let mut fresh: Vec<u64> = Vec::new();
+    for _ in 0..1000 {
+        let seed: u64 = rng.gen_unique_seed(&fresh);
+        assert!(!fresh.contains(&seed));
+        fresh.push(seed);
+    }
+}
+
+#[test]
+pub fn test_clustered_mask() {
+    let mut rng: SquaresRNG = SquaresRNG::new_with_key(0, SquaresRNG::TEST_KEY);
+
+    // Fraction of set cells that have at least one set neighbor
+    let connected = |mask: &[Vec<bool>]| -> f64 {
+        let (width, height) = (mask[0].len(), mask.len());
+        let mut set: u64 = 0;
+        let mut touching: u64 = 0;
+        for y in 0..height {
+            for x in 0..width {
+                if !mask[y][x] {
+                    continue;
+                }
+                set += 1;
+                let neighbor: bool = (x > 0 && mask[y][x - 1]) || (x + 1 < width && mask[y][x + 1]) || (y > 0 && mask[y - 1][x]) || (y + 1 < height && mask[y + 1][x]);
+                if neighbor {
+                    touching += 1;
+                }
+            }
+        }
+        touching as f64 / set as f64
+    };
+
+    let clustered: Vec<Vec<bool>> = rng.clustered_mask(128, 96, 0.2, 0.95);
+    let scattered: Vec<Vec<bool>> = rng.clustered_mask(128, 96, 0.2, 0.0);
+
+    assert_eq!(clustered.len(), 96);
+    assert!(clustered.iter().all(|row| row.len() == 128));
+
+    let fraction: f64 = clustered.iter().flatten().filter(|cell| **cell).count() as f64 / (128 * 96) as f64;
+    let clustered_connected: f64 = connected(&clustered);
+    let scattered_connected: f64 = connected(&scattered);
+    println!("\nRNG Clustered Mask (Closer to 0.2, then clustered higher): {}, clustered: {}, scattered: {}\n", fraction, clustered_connected, scattered_connected);
+
+    assert!(fraction < 0.21 && fraction > 0.19);
+    assert!(clustered_connected > 0.9);
+    assert!(clustered_connected > scattered_connected + 0.2);
+
+    let full: Vec<Vec<bool>> = rng.clustered_mask(10, 10, 1.0, 0.5);
+    assert!(full.iter().flatten().all(|cell| *cell));
+}
+
+#[test]
+pub fn test_tournament_select() {
+    let mut rng: SquaresRNG = SquaresRNG::new_with_key(0, SquaresRNG::TEST_KEY);
+
+    let population: [u32; 10] = [0, 1, 2, 3, 4, 5, 6, 7, 8, 9];
+    let fitness = |value: &u32| *value as f64;
+
+    let count: u64 = 200_000;
+    let mut uniform: [u64; 10] = [0; 10];
+    let mut best_k2: u64 = 0;
+    let mut best_k5: u64 = 0;
+
+    for _ in 0..count {
+        uniform[*rng.tournament_select(&population, 1, fitness).unwrap() as usize] += 1;
+        if *rng.tournament_select(&population, 2, fitness).unwrap() == 9 {
+            best_k2 += 1;
+        }
+        if *rng.tournament_select(&population, 5, fitness).unwrap() == 9 {
+            best_k5 += 1;
+        }
+    }
+
+    // The best of k uniform picks is the top element with a chance of 1 - 0.9^k
+    let best_k2: f64 = best_k2 as f64 / count as f64;
+    let best_k5: f64 = best_k5 as f64 / count as f64;
+    println!("\nRNG Tournament Select (Closer to 20000 each, then 0.19 and 0.41): {:?}, {}, {}\n", uniform, best_k2, best_k5);
+
+    assert!(uniform.iter().all(|u| *u < 20_800 && *u > 19_200));
+    assert!(best_k2 < 0.195 && best_k2 > 0.185);
+    assert!(best_k5 < 0.415 && best_k5 > 0.405);
+
+    let empty: [u32; 0] = [];
+    assert_eq!(rng.tournament_select(&empty, 3, fitness), None);
+    assert_eq!(rng.tournament_select(&population, 0, fitness), None);
+}
+
+#[test]
+pub fn test_preview_distribution() {
+    let mut rng: SquaresRNG = SquaresRNG::new_with_key(0, SquaresRNG::TEST_KEY);
+
+    let counts: Vec<u64> = rng.preview_distribution(1_000_000, 10, |rng| rng.rand_index(100) as i64 - 50);
+    println!("\nRNG Preview Distribution (Closer to 100000 each): {:?}\n", counts);
+
+    assert_eq!(counts.len(), 10);
+    assert_eq!(counts.iter().sum::<u64>(), 1_000_000);
+    assert!(counts.iter().all(|c| *c < 102_000 && *c > 98_000));
+
+    let constant: Vec<u64> = rng.preview_distribution(100, 4, |_| 7);
+    assert_eq!(constant, vec![100, 0, 0, 0]);
+    assert!(rng.preview_distribution(100, 0, |rng| rng.rand_index(10) as i64).is_empty());
+}
+
+#[test]
+pub fn test_random_orientation_steps() {
+    let mut rng: SquaresRNG = SquaresRNG::new_with_key(0, SquaresRNG::TEST_KEY);
+
+    let count: u64 = 600_000;
+    let mut sixths: [u64; 6] = [0; 6];
+    let mut cardinals: [u64; 4] = [0; 4];
+
+    for _ in 0..count {
+        let step: u32 = rng.random_orientation_steps(6);
+        assert!(step < 6);
+        sixths[step as usize] += 1;
+
+        let cardinal: u32 = rng.random_cardinal();
+        assert!(cardinal < 4);
+        cardinals[cardinal as usize] += 1;
+    }
+
+    println!("\nRNG Orientation Steps (Closer to 100000 and 150000 each): {:?}, {:?}\n", sixths, cardinals);
+
+    assert!(sixths.iter().all(|s| *s < 101_500 && *s > 98_500));
+    assert!(cardinals.iter().all(|c| *c < 151_500 && *c > 148_500));
+    assert_eq!(rng.random_orientation_steps(0), 0);
+}
+
+#[test]
+pub fn test_antithetic_pair_f64() {
+    let mut rng: SquaresRNG = SquaresRNG::new_with_key(0, SquaresRNG::TEST_KEY);
+
+    let count: u64 = 100_000;
+    let f = |x: f64| x.exp();
+
+    let mut antithetic: Vec<f64> = Vec::with_capacity(count as usize);
+    let mut independent: Vec<f64> = Vec::with_capacity(count as usize);
+
+    for _ in 0..count {
+        let counter: u64 = rng.counter;
+        let (u, v) = rng.antithetic_pair_f64();
+        assert_eq!(rng.counter, counter + 1);
+        assert!((u + v - 1.0).abs() < 1e-12);
+
+        antithetic.push((f(u) + f(v)) * 0.5);
+        independent.push((f(rng.randf64()) + f(rng.randf64())) * 0.5);
+    }
+
+    let variance = |values: &[f64]| -> f64 {
+        let mean: f64 = values.iter().sum::<f64>() / values.len() as f64;
+        values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / values.len() as f64
+    };
+
+    let antithetic_variance: f64 = variance(&antithetic);
+    let independent_variance: f64 = variance(&independent);
+    println!("\nRNG Antithetic Variance (Antithetic lower): antithetic: {}, independent: {}\n", antithetic_variance, independent_variance);
+
+    assert!(antithetic_variance < independent_variance * 0.1);
+}
+
+#[test]
+pub fn test_strided_sequence() {
+    let mut rng: SquaresRNG = SquaresRNG::new_with_key(0, SquaresRNG::TEST_KEY);
+
+    for modulus in [1, 2, 7, 12, 100, 360] {
+        for _ in 0..50 {
+            let sequence: Vec<usize> = rng.strided_sequence(modulus, modulus);
+            assert_eq!(sequence.len(), modulus);
+
+            let mut seen: Vec<bool> = vec![false; modulus];
+            for value in sequence {
+                assert!(!seen[value]);
+                seen[value] = true;
+            }
+            assert!(seen.iter().all(|s| *s));
+        }
+    }
+
+    let repeated: Vec<usize> = rng.strided_sequence(24, 12);
+    assert_eq!(repeated[..12], repeated[12..]);
+    assert!(rng.strided_sequence(5, 0).is_empty());
+}
+
+#[test]
+pub fn test_hysteresis_bool() {
+    let mut rng: SquaresRNG = SquaresRNG::new_with_key(0, SquaresRNG::TEST_KEY);
+    let mut toggle: HysteresisBool = HysteresisBool::new(0.1, 0.05);
+
+    let count: u64 = 1_000_000;
+    let mut steps: [u64; 2] = [0; 2];
+    let mut runs: [u64; 2] = [0; 2];
+    let mut flips: u64 = 0;
+    let mut independent_flips: u64 = 0;
+
+    let mut last: bool = toggle.state;
+    let mut independent_last: bool = false;
+    for _ in 0..count {
+        let state: bool = toggle.next(&mut rng);
+        steps[state as usize] += 1;
+        if state != last {
+            runs[state as usize] += 1;
+            flips += 1;
+        }
+        last = state;
+
+        // Independent coin flips that are on for the same share of the time
+        let independent: bool = rng.randf64() < 2.0 / 3.0;
+        if independent != independent_last {
+            independent_flips += 1;
+        }
+        independent_last = independent;
+    }
+
+    let off_dwell: f64 = steps[0] as f64 / runs[0].max(1) as f64;
+    let on_dwell: f64 = steps[1] as f64 / runs[1].max(1) as f64;
+    println!("\nRNG Hysteresis Bool Dwell (Closer to 10 and 20): off: {}, on: {}, flips: {}, independent flips: {}\n", off_dwell, on_dwell, flips, independent_flips);
+
+    assert!(off_dwell < 10.3 && off_dwell > 9.7);
+    assert!(on_dwell < 20.6 && on_dwell > 19.4);
+    assert!(flips * 4 < independent_flips);
+}
+
+#[test]
+pub fn test_global_shuffle_bag() {
+    let mut rng: SquaresRNG = SquaresRNG::new_with_key(0, SquaresRNG::TEST_KEY);
+    let mut bag: GlobalShuffleBag = GlobalShuffleBag::new(12);
+    assert_eq!(bag.range(), 12);
+
+    let mut cycles: Vec<Vec<usize>> = Vec::new();
+    for _ in 0..20 {
+        let mut cycle: Vec<usize> = (0..12).map(|_| bag.next(&mut rng)).collect();
+        cycles.push(cycle.clone());
+
+        cycle.sort_unstable();
+        assert_eq!(cycle, (0..12).collect::<Vec<usize>>());
+    }
+
+    // A fresh shuffle starts after each cycle, so the cycles shouldn't all be in the same order
+    assert!(cycles.windows(2).any(|pair| pair[0] != pair[1]));
+}
+
+#[test]
+#[should_panic(expected = "GlobalShuffleBag range must be greater than 0")]
+pub fn test_global_shuffle_bag_empty() {
+    GlobalShuffleBag::new(0);
+}
+
+#[test]
+pub fn test_bounded_power_law() {
+    let mut rng: SquaresRNG = SquaresRNG::new_with_key(0, SquaresRNG::TEST_KEY);
+
+    let count: u64 = 1_000_000;
+    let mut steep_small: u64 = 0;
+    let mut flat_small: u64 = 0;
+
+    for _ in 0..count {
+        let steep: f64 = rng.bounded_power_law(1.0, 100.0, 3.0);
+        let flat: f64 = rng.bounded_power_law(1.0, 100.0, 1.0);
+        assert!((1.0..=100.0).contains(&steep));
+        assert!((1.0..=100.0).contains(&flat));
+
+        if steep < 2.0 {
+            steep_small += 1;
+        }
+        if flat < 2.0 {
+            flat_small += 1;
+        }
+    }
+
+    // CDF at 2 is (1 - 2^-2) / (1 - 100^-2) for exponent 3, and ln(2) / ln(100) for exponent 1
+    let steep_small: f64 = steep_small as f64 / count as f64;
+    let flat_small: f64 = flat_small as f64 / count as f64;
+    println!("\nRNG Bounded Power Law Below 2 (Closer to 0.750 and 0.151): {}, {}\n", steep_small, flat_small);
+
+    assert!(steep_small < 0.755 && steep_small > 0.745);
+    assert!(flat_small < 0.156 && flat_small > 0.146);
+    assert!(rng.bounded_power_law(0.0, 1.0, 2.0).is_nan());
+    assert!(rng.bounded_power_law(5.0, 1.0, 2.0).is_nan());
+}
+
+#[test]
+pub fn test_gradient_biased_direction() {
+    let mut rng: SquaresRNG = SquaresRNG::new_with_key(0, SquaresRNG::TEST_KEY);
+
+    let gradient: (f32, f32) = (3.0, -4.0);
+    let downhill: (f32, f32) = (-0.6, 0.8);
+
+    let count: u64 = 100_000;
+    let mut alignments: Vec<f32> = Vec::new();
+
+    for bias in [0.0, 0.25, 0.5, 0.75, 1.0] {
+        let mut alignment: f32 = 0.0;
+        for _ in 0..count {
+            let direction: (f32, f32) = rng.gradient_biased_direction(gradient, bias);
+            assert!(((direction.0 * direction.0 + direction.1 * direction.1).sqrt() - 1.0).abs() < 1e-5);
+            alignment += direction.0 * downhill.0 + direction.1 * downhill.1;
+        }
+        alignments.push(alignment / count as f32);
+    }
+
+    println!("\nRNG Gradient Biased Direction Alignment (Increasing, from 0.0 to 1.0): {:?}\n", alignments);
+
+    assert!(alignments[0].abs() < 0.01);
+    assert!(alignments.windows(2).all(|pair| pair[0] < pair[1]));
+    assert!(alignments[4] > 0.9999);
+}
+
+#[test]
+pub fn test_timed_chooser() {
+    let mut rng: SquaresRNG = SquaresRNG::new_with_key(0, SquaresRNG::TEST_KEY);
+    let mut chooser: TimedChooser = TimedChooser::new(10.0);
+
+    let lines: [&str; 4] = ["hello", "watch out", "over here", "reloading"];
+    let mut history: Vec<(f64, &str)> = Vec::new();
+
+    // One pick every 3 seconds, so the last 3 picks are always cooling down and exactly one line is ready
+    for step in 0..1000 {
+        let now: f64 = step as f64 * 3.0;
+        if let Some(line) = chooser.choose(&mut rng, &lines, now) {
+            assert!(history.iter().all(|(time, used)| used != line || now - time >= 10.0));
+            history.push((now, line));
+        }
+    }
+
+    assert_eq!(history.len(), 1000);
+
+    // Everything is cooling down, until the cooldown elapses
+    let mut chooser: TimedChooser = TimedChooser::new(5.0);
+    let single: [u32; 1] = [7];
+    assert_eq!(chooser.choose(&mut rng, &single, 0.0), Some(&7));
+    assert_eq!(chooser.choose(&mut rng, &single, 4.9), None);
+    assert_eq!(chooser.choose(&mut rng, &single, 5.0), Some(&7));
+}
+
+#[test]
+pub fn test_room_corridors() {
+    let mut rng: SquaresRNG = SquaresRNG::new_with_key(0, SquaresRNG::TEST_KEY);
+
+    let reachable = |count: usize, corridors: &[(usize, usize)]| -> bool {
+        let mut visited: Vec<bool> = vec![false; count];
+        let mut stack: Vec<usize> = vec![0];
+        while let Some(room) = stack.pop() {
+            if visited[room] {
+                continue;
+            }
+            visited[room] = true;
+            for (a, b) in corridors {
+                if *a == room { stack.push(*b); }
+                if *b == room { stack.push(*a); }
+            }
+        }
+        visited.iter().all(|v| *v)
+    };
+
+    for _ in 0..20 {
+        let rooms: Vec<(f32, f32)> = (0..40).map(|_| (rng.rangef32(0.0, 200.0), rng.rangef32(0.0, 100.0))).collect();
+        let seed: SquaresRNG = SquaresRNG::new_with_key(rng.counter, rng.key);
+
+        let corridors: Vec<(usize, usize)> = rng.room_corridors(&rooms);
+        assert!(reachable(rooms.len(), &corridors));
+        assert!(corridors.iter().all(|(a, b)| a < b && *b < rooms.len()));
+
+        // A triangulation of n points has at most 3n - 6 edges, n - 1 of which are in the spanning tree
+        let loops: usize = corridors.len() - (rooms.len() - 1);
+        let most: usize = ((3 * rooms.len() - 6 - (rooms.len() - 1)) as f64 * SquaresRNG::CORRIDOR_LOOP_FRACTION).round() as usize;
+        assert!(loops > 0 && loops <= most);
+
+        let mut again: SquaresRNG = seed;
+        assert_eq!(again.room_corridors(&rooms), corridors);
+    }
+
+    let line: Vec<(f32, f32)> = (0..6).map(|i| (i as f32, 0.0)).collect();
+    assert!(reachable(line.len(), &rng.room_corridors(&line)));
+    assert!(rng.room_corridors(&[(0.0, 0.0)]).is_empty());
+}
+
+#[test]
+pub fn test_sample_masked_policy() {
+    let mut rng: SquaresRNG = SquaresRNG::new_with_key(0, SquaresRNG::TEST_KEY);
+
+    let logits: [f64; 4] = [4.0, 1.0, 2.0, 3.0];
+    let mask: [bool; 4] = [false, true, true, true];
+
+    let count: u64 = 600_000;
+    let mut actions: [u64; 4] = [0; 4];
+
+    for _ in 0..count {
+        actions[rng.sample_masked_policy(&logits, &mask).unwrap()] += 1;
+    }
+
+    // The masked action's weight is dropped, leaving 1 : 2 : 3
+    println!("\nRNG Masked Policy (Closer to 0, 100000, 200000, 300000): {:?}\n", actions);
+
+    assert_eq!(actions[0], 0);
+    assert!(actions[1] < 101_500 && actions[1] > 98_500);
+    assert!(actions[2] < 202_000 && actions[2] > 198_000);
+    assert!(actions[3] < 302_000 && actions[3] > 298_000);
+    assert_eq!(rng.sample_masked_policy(&logits, &[false; 4]), None);
+    assert_eq!(rng.sample_masked_policy(&logits, &[true; 3]), None);
+}
+
+#[test]
+pub fn test_chunk_rng() {
+    let world_seed: u64 = 0xdeadbeef;
+
+    let content = |x: i32, z: i32| -> Vec<f64> {
+        let mut rng: SquaresRNG = SquaresRNG::chunk_rng(world_seed, x, z);
+        (0..1000).map(|_| rng.randf64()).collect()
+    };
+
+    let forward: Vec<Vec<f64>> = (-2..=2).flat_map(|x| (-2..=2).map(move |z| (x, z))).map(|(x, z)| content(x, z)).collect();
+    let mut backward: Vec<Vec<f64>> = (-2..=2).rev().flat_map(|x| (-2..=2).rev().map(move |z| (x, z))).map(|(x, z)| content(x, z)).collect();
+    backward.reverse();
+    assert_eq!(forward, backward);
+
+    // Neighboring chunks should be uncorrelated
+    let correlation = |a: &[f64], b: &[f64]| -> f64 {
+        let mean_a: f64 = a.iter().sum::<f64>() / a.len() as f64;
+        let mean_b: f64 = b.iter().sum::<f64>() / b.len() as f64;
+        let covariance: f64 = a.iter().zip(b).map(|(x, y)| (x - mean_a) * (y - mean_b)).sum::<f64>();
+        let spread_a: f64 = a.iter().map(|x| (x - mean_a).powi(2)).sum::<f64>().sqrt();
+        let spread_b: f64 = b.iter().map(|y| (y - mean_b).powi(2)).sum::<f64>().sqrt();
+        covariance / (spread_a * spread_b)
+    };
+
+    let origin: Vec<f64> = content(0, 0);
+    for (x, z) in [(1, 0), (0, 1), (-1, 0), (0, -1), (1, 1)] {
+        let neighbor: Vec<f64> = content(x, z);
+        let result: f64 = correlation(&origin, &neighbor);
+        println!("\nRNG Chunk Correlation (Closer to 0.0): ({}, {}): {}\n", x, z, result);
+        assert_ne!(origin, neighbor);
+        assert!(result.abs() < 0.1);
+    }
+
+    assert_ne!(SquaresRNG::chunk_rng(world_seed, 3, 4).key, SquaresRNG::chunk_rng(world_seed + 1, 3, 4).key);
+}
+
+#[test]
+pub fn test_random_sized_subset() {
+    let mut rng: SquaresRNG = SquaresRNG::new_with_key(0, SquaresRNG::TEST_KEY);
+
+    let units: Vec<u32> = (0..50).collect();
+
+    let count: u64 = 20_000;
+    let mut total: u64 = 0;
+    let mut sizes: Vec<usize> = Vec::new();
+
+    for _ in 0..count {
+        let elites: Vec<&u32> = rng.random_sized_subset(&units, 0.2);
+        assert!(elites.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(elites.iter().all(|elite| units.iter().any(|unit| core::ptr::eq(unit, *elite))));
+
+        total += elites.len() as u64;
+        sizes.push(elites.len());
+    }
+
+    let mean: f64 = total as f64 / count as f64;
+    println!("\nRNG Random Sized Subset Mean Size (Closer to 10.0): {}\n", mean);
+
+    assert!(mean < 10.1 && mean > 9.9);
+    assert!(sizes.iter().any(|size| *size != sizes[0]));
+}
+
+#[test]
+pub fn test_color_walk_rgb8() {
+    let mut rng: SquaresRNG = SquaresRNG::new_with_key(0, SquaresRNG::TEST_KEY);
+
+    for delta in [0, 1, 8, 255] {
+        let colors: Vec<[u8; 3]> = rng.color_walk_rgb8(1000, delta);
+        assert_eq!(colors.len(), 1000);
+
+        for pair in colors.windows(2) {
+            for (a, b) in pair[0].iter().zip(pair[1].iter()) {
+                assert!((*a as i32 - *b as i32).unsigned_abs() <= delta as u32);
+            }
+        }
+
+        if delta > 0 {
+            assert!(colors.iter().any(|color| *color != colors[0]));
+        }
+    }
+
+    assert!(rng.color_walk_rgb8(0, 4).is_empty());
+}
+
+#[test]
+pub fn test_roll_cumulative() {
+    let mut rng: SquaresRNG = SquaresRNG::new_with_key(0, SquaresRNG::TEST_KEY);
+
+    let thresholds: [u32; 3] = [10, 30, 100];
+
+    let count: u64 = 1_000_000;
+    let mut buckets: [u64; 3] = [0; 3];
+
+    for _ in 0..count {
+        buckets[rng.roll_cumulative(&thresholds).unwrap()] += 1;
+    }
+
+    let buckets: Vec<f64> = buckets.iter().map(|b| *b as f64 / count as f64).collect();
+    println!("\nRNG Cumulative Thresholds (Closer to 0.1, 0.2, 0.7): {:?}\n", buckets);
+
+    assert!(buckets[0] < 0.102 && buckets[0] > 0.098);
+    assert!(buckets[1] < 0.202 && buckets[1] > 0.198);
+    assert!(buckets[2] < 0.702 && buckets[2] > 0.698);
+    assert_eq!(rng.roll_cumulative(&[]), None);
+    assert_eq!(rng.roll_cumulative(&[10, 10, 20]), None);
+    assert_eq!(rng.roll_cumulative(&[30, 20]), None);
+    assert_eq!(rng.roll_cumulative(&[0, 5]), Some(1));
+}
+
+#[test]
+pub fn test_gaussian_grid_u8() {
+    let mut rng: SquaresRNG = SquaresRNG::new_with_key(0, SquaresRNG::TEST_KEY);
+
+    let grid: Vec<Vec<u8>> = rng.gaussian_grid_u8(320, 240, 100.0, 20.0);
+    assert_eq!(grid.len(), 240);
+    assert!(grid.iter().all(|row| row.len() == 320));
+
+    let cells: f64 = (320 * 240) as f64;
+    let mean: f64 = grid.iter().flatten().map(|value| *value as f64).sum::<f64>() / cells;
+    let deviation: f64 = (grid.iter().flatten().map(|value| (*value as f64 - mean).powi(2)).sum::<f64>() / cells).sqrt();
+    println!("\nRNG Gaussian Grid (Closer to 100 and 20): mean: {}, deviation: {}\n", mean, deviation);
+
+    assert!(mean < 100.5 && mean > 99.5);
+    assert!(deviation < 20.5 && deviation > 19.5);
+
+    // Values far outside 0..=255 are clamped
+    let clamped: Vec<Vec<u8>> = rng.gaussian_grid_u8(64, 64, 250.0, 40.0);
+    assert!(clamped.iter().flatten().any(|value| *value == 255));
+}
+
+#[test]
+pub fn test_on_rect_perimeter_f32() {
+    let mut rng: SquaresRNG = SquaresRNG::new_with_key(0, SquaresRNG::TEST_KEY);
+
+    let (width, height) = (300.0, 100.0);
+
+    let count: u64 = 1_000_000;
+    let mut edges: [u64; 4] = [0; 4];
+
+    for _ in 0..count {
+        let (x, y) = rng.on_rect_perimeter_f32(width, height);
+        assert!((0.0..=width).contains(&x) && (0.0..=height).contains(&y));
+
+        if y == 0.0 {
+            edges[0] += 1;
+        } else if x == width {
+            edges[1] += 1;
+        } else if y == height {
+            edges[2] += 1;
+        } else if x == 0.0 {
+            edges[3] += 1;
+        } else {
+            panic!("({}, {}) is not on the perimeter", x, y);
+        }
+    }
+
+    let edges: Vec<f64> = edges.iter().map(|e| *e as f64 / count as f64).collect();
+    println!("\nRNG Rect Perimeter Edges (Closer to 0.375, 0.125, 0.375, 0.125): {:?}\n", edges);
+
+    assert!(edges[0] < 0.378 && edges[0] > 0.372);
+    assert!(edges[1] < 0.128 && edges[1] > 0.122);
+    assert!(edges[2] < 0.378 && edges[2] > 0.372);
+    assert!(edges[3] < 0.128 && edges[3] > 0.122);
+}
+
+#[test]
+pub fn test_fill_budget() {
+    let mut rng: SquaresRNG = SquaresRNG::new_with_key(0, SquaresRNG::TEST_KEY);
+
+    let items: [(&str, u32); 6] = [("sword", 40), ("shield", 35), ("potion", 5), ("bow", 30), ("armor", 60), ("ring", 15)];
+    let cost = |item: &(&str, u32)| item.1;
+
+    let mut loadouts: Vec<Vec<&str>> = Vec::new();
+    for _ in 0..1000 {
+        let picked: Vec<&(&str, u32)> = rng.fill_budget(&items, 100, cost);
+        let total: u32 = picked.iter().map(|item| item.1).sum();
+        assert!(total <= 100);
+
+        // Nothing that is left over would still fit
+        let left: u32 = 100 - total;
+        assert!(items.iter().filter(|item| !picked.contains(item)).all(|item| item.1 > left));
+
+        loadouts.push(picked.iter().map(|item| item.0).collect());
+    }
+
+    assert!(loadouts.iter().any(|loadout| *loadout != loadouts[0]));
+
+    let mut other: SquaresRNG = SquaresRNG::new_with_key(12345, SquaresRNG::TEST_KEY);
+    let mut again: SquaresRNG = SquaresRNG::new_with_key(12345, SquaresRNG::TEST_KEY);
+    assert_eq!(other.fill_budget(&items, 100, cost), again.fill_budget(&items, 100, cost));
+    assert!(rng.fill_budget(&items, 4, cost).is_empty());
+}
+
+#[test]
+pub fn test_discrete_normal() {
+    let mut rng: SquaresRNG = SquaresRNG::new_with_key(0, SquaresRNG::TEST_KEY);
+
+    let count: u64 = 1_000_000;
+    let mut values: [u64; 21] = [0; 21];
+
+    for _ in 0..count {
+        let value: i64 = rng.discrete_normal(-10, 10);
+        assert!((-10..=10).contains(&value));
+        values[(value + 10) as usize] += 1;
+    }
+
+    let mode: usize = values.iter().enumerate().max_by_key(|(_, v)| **v).unwrap().0;
+    println!("\nRNG Discrete Normal (Mode closer to 10): mode: {}, {:?}\n", mode, values);
+
+    assert!((9..=11).contains(&mode));
+    assert!(values[0] * 100 < values[10]);
+    assert!(values[20] * 100 < values[10]);
+    assert!(values[..10].windows(2).all(|pair| pair[0] < pair[1]));
+    assert!(values[11..].windows(2).all(|pair| pair[0] > pair[1]));
+    assert_eq!(rng.discrete_normal(5, 5), 5);
+}
+
+#[cfg(feature = "std")]
+#[test]
+pub fn test_shuffled_keys() {
+    use std::collections::HashMap;
+
+    // Each map gets its own random hasher state, so their iteration orders differ
+    let maps: Vec<HashMap<u32, &str>> = (0..10).map(|_| (0..50).map(|key| (key, "value")).collect()).collect();
+
+    let orders: Vec<Vec<u32>> = maps.iter().map(|map| {
+        let mut rng: SquaresRNG = SquaresRNG::new_with_key(0, SquaresRNG::TEST_KEY);
+        rng.shuffled_keys(map)
+    }).collect();
+
+    assert!(orders.iter().all(|order| *order == orders[0]));
+
+    let mut sorted: Vec<u32> = orders[0].clone();
+    sorted.sort_unstable();
+    assert_eq!(sorted, (0..50).collect::<Vec<u32>>());
+    assert_ne!(orders[0], sorted);
+
+    let mut rng: SquaresRNG = SquaresRNG::new_with_key(0, SquaresRNG::TEST_KEY);
+    rng.shuffled_keys(&maps[0]);
+    assert_ne!(rng.shuffled_keys(&maps[0]), orders[0]);
+}
+
+#[test]
+pub fn test_epsilon_greedy() {
+    let mut rng: SquaresRNG = SquaresRNG::new_with_key(0, SquaresRNG::TEST_KEY);
+
+    let arms: [f64; 5] = [0.2, 0.9, 0.4, 0.1, 0.5];
+    let value = |arm: &f64| *arm;
+
+    let count: u64 = 500_000;
+    let mut greedy: [u64; 5] = [0; 5];
+    let mut explore: [u64; 5] = [0; 5];
+    let mut mixed_best: u64 = 0;
+
+    for _ in 0..count {
+        let pick = |arm: &f64| arms.iter().position(|a| a == arm).unwrap();
+        greedy[pick(rng.epsilon_greedy(&arms, 0.0, value).unwrap())] += 1;
+        explore[pick(rng.epsilon_greedy(&arms, 1.0, value).unwrap())] += 1;
+        if pick(rng.epsilon_greedy(&arms, 0.3, value).unwrap()) == 1 {
+            mixed_best += 1;
+        }
+    }
+
+    // With epsilon 0.3, the best arm is picked 70% of the time plus its share of the random picks
+    let mixed_best: f64 = mixed_best as f64 / count as f64;
+    println!("\nRNG Epsilon Greedy (Closer to 100000 each, then 0.76): {:?}, {}\n", explore, mixed_best);
+
+    assert_eq!(greedy, [0, count, 0, 0, 0]);
+    assert!(explore.iter().all(|e| *e < 101_500 && *e > 98_500));
+    assert!(mixed_best < 0.765 && mixed_best > 0.755);
+
+    let empty: [f64; 0] = [];
+    assert_eq!(rng.epsilon_greedy(&empty, 0.5, value), None);
+}
+
+#[test]
+pub fn test_midpoint_displacement_1d() {
+    let mut rng: SquaresRNG = SquaresRNG::new_with_key(0, SquaresRNG::TEST_KEY);
+
+    let profile: Vec<f32> = rng.midpoint_displacement_1d(100, 0.5);
+    assert_eq!(profile.len(), 129);
+    assert_eq!(profile[0], 0.0);
+    assert_eq!(profile[128], 0.0);
+    assert_eq!(SquaresRNG::new_with_key(0, SquaresRNG::TEST_KEY).midpoint_displacement_1d(100, 0.5), profile);
+
+    assert_eq!(rng.midpoint_displacement_1d(0, 0.5).len(), 2);
+    assert_eq!(rng.midpoint_displacement_1d(257, 0.5).len(), 257);
+
+    // Variance of the differences between neighbors, which grows with roughness
+    let jaggedness = |profile: &[f32]| -> f32 {
+        let steps: Vec<f32> = profile.windows(2).map(|pair| pair[1] - pair[0]).collect();
+        steps.iter().map(|s| s * s).sum::<f32>() / steps.len() as f32
+    };
+
+    let mut smooth: f32 = 0.0;
+    let mut rough: f32 = 0.0;
+    for _ in 0..100 {
+        smooth += jaggedness(&rng.midpoint_displacement_1d(257, 0.4));
+        rough += jaggedness(&rng.midpoint_displacement_1d(257, 0.8));
+    }
+
+    println!("\nRNG Midpoint Displacement Jaggedness (Rough higher): smooth: {}, rough: {}\n", smooth, rough);
+    assert!(rough > smooth * 10.0);
+}
+
+#[test]
+pub fn test_charge_trigger() {
+    let mut rng: SquaresRNG = SquaresRNG::new_with_key(0, SquaresRNG::TEST_KEY);
+
+    let count: u64 = 200_000;
+    let mut fired: [u64; 3] = [0; 3];
+
+    for (slot, charge) in [1.0, 3.0, 6.0].iter().enumerate() {
+        for _ in 0..count {
+            let mut trigger: ChargeTrigger = ChargeTrigger::new(0.1);
+            trigger.add_charge(*charge);
+            if trigger.try_trigger(&mut rng) {
+                fired[slot] += 1;
+                assert_eq!(trigger.charge, 0.0);
+            } else {
+                assert_eq!(trigger.charge, *charge);
+            }
+        }
+    }
+
+    let fired: Vec<f64> = fired.iter().map(|f| *f as f64 / count as f64).collect();
+    println!("\nRNG Charge Trigger (Closer to 0.1, 0.3, 0.6): {:?}\n", fired);
+
+    assert!(fired[0] < 0.103 && fired[0] > 0.097);
+    assert!(fired[1] < 0.303 && fired[1] > 0.297);
+    assert!(fired[2] < 0.603 && fired[2] > 0.597);
+
+    let mut full: ChargeTrigger = ChargeTrigger::new(0.1);
+    full.add_charge(10.0);
+    assert!(full.try_trigger(&mut rng));
+    assert!(!full.try_trigger(&mut rng));
+}
+
+#[test]
+pub fn test_random_palette_map() {
+    let mut rng: SquaresRNG = SquaresRNG::new_with_key(0, SquaresRNG::TEST_KEY);
+
+    let palette: [[u8; 3]; 5] = [[220, 40, 40], [40, 200, 60], [30, 60, 230], [250, 200, 20], [128, 128, 128]];
+
+    for _ in 0..1000 {
+        let mapped: Vec<[u8; 3]> = rng.random_palette_map(&palette, 30.0);
+        assert_eq!(mapped.len(), palette.len());
+
+        for (original, recolored) in palette.iter().zip(mapped.iter()) {
+            let (hue, saturation, value) = SquaresRNG::rgb8_to_hsv(*original);
+            let (new_hue, new_saturation, new_value) = SquaresRNG::rgb8_to_hsv(*recolored);
+
+            if saturation == 0.0 {
+                assert_eq!(original, recolored);
+                continue;
+            }
+
+            // Rounding to bytes moves things slightly
+            let shift: f32 = (new_hue - hue + 180.0).rem_euclid(360.0) - 180.0;
+            assert!(shift.abs() <= 31.0, "hue shifted by {}", shift);
+            assert!((new_saturation - saturation).abs() < 0.02);
+            assert!((new_value - value).abs() < 0.01);
+        }
+    }
+}
+
+#[test]
+pub fn test_gaussian2d_covariance() {
+    let mut rng: SquaresRNG = SquaresRNG::new_with_key(0, SquaresRNG::TEST_KEY);
+
+    let mean: (f64, f64) = (5.0, -3.0);
+    let cov: [[f64; 2]; 2] = [[4.0, 1.5], [1.5, 1.0]];
+
+    let count: usize = 1_000_000;
+    let points: Vec<(f64, f64)> = (0..count).map(|_| rng.gaussian2d_covariance(mean, cov)).collect();
+
+    let mean_x: f64 = points.iter().map(|p| p.0).sum::<f64>() / count as f64;
+    let mean_y: f64 = points.iter().map(|p| p.1).sum::<f64>() / count as f64;
+    let xx: f64 = points.iter().map(|p| (p.0 - mean_x).powi(2)).sum::<f64>() / count as f64;
+    let xy: f64 = points.iter().map(|p| (p.0 - mean_x) * (p.1 - mean_y)).sum::<f64>() / count as f64;
+    let yy: f64 = points.iter().map(|p| (p.1 - mean_y).powi(2)).sum::<f64>() / count as f64;
+
+    println!("\nRNG Gaussian 2D (Closer to (5, -3) and [[4, 1.5], [1.5, 1]]): ({}, {}), [[{}, {}], [{}, {}]]\n", mean_x, mean_y, xx, xy, xy, yy);
+
+    assert!((mean_x - 5.0).abs() < 0.01 && (mean_y + 3.0).abs() < 0.01);
+    assert!((xx - 4.0).abs() < 0.03);
+    assert!((xy - 1.5).abs() < 0.02);
+    assert!((yy - 1.0).abs() < 0.01);
+
+    let singular: (f64, f64) = rng.gaussian2d_covariance(mean, [[1.0, 2.0], [2.0, 4.0]]);
+    assert!(singular.0.is_nan() && singular.1.is_nan());
+    assert!(rng.gaussian2d_covariance(mean, [[1.0, 0.5], [0.2, 1.0]]).0.is_nan());
+}
+
+#[test]
+pub fn test_streak_breaker() {
+    let mut rng: SquaresRNG = SquaresRNG::new_with_key(0, SquaresRNG::TEST_KEY);
+
+    let weights: [f64; 3] = [2.0, 1.0, 1.0];
+    let mut breaker: StreakBreaker = StreakBreaker::new(&weights, 0.5);
+
+    let count: u64 = 1_000_000;
+    let mut broken: [u64; 3] = [0; 3];
+    let mut longest: (u64, u64) = (0, 0);
+    let mut run: (u64, u64) = (0, 0);
+    let mut last: (usize, usize) = (usize::MAX, usize::MAX);
+
+    for _ in 0..count {
+        let index: usize = breaker.choose(&mut rng).unwrap();
+        broken[index] += 1;
+        run.0 = if index == last.0 { run.0 + 1 } else { 1 };
+        last.0 = index;
+        longest.0 = longest.0.max(run.0);
+
+        let index: usize = rng.weighted_index(&weights).unwrap();
+        run.1 = if index == last.1 { run.1 + 1 } else { 1 };
+        last.1 = index;
+        longest.1 = longest.1.max(run.1);
+    }
+
+    let broken: Vec<f64> = broken.iter().map(|b| *b as f64 / count as f64).collect();
+    println!("\nRNG Streak Breaker (Closer to 0.5, 0.25, 0.25, then shorter runs): {:?}, longest: {}, unmodified longest: {}\n", broken, longest.0, longest.1);
+
+    assert!(longest.0 * 2 < longest.1);
+    assert!(broken[0] < 0.5 && broken[0] > 0.44);
+    assert!(broken[1] < 0.29 && broken[1] > 0.25);
+    assert!(broken[2] < 0.29 && broken[2] > 0.25);
+
+    // Shrinking the weights after a pick must not panic
+    let mut breaker: StreakBreaker = StreakBreaker::new(&[0.0, 0.0, 1.0], 0.5);
+    assert_eq!(breaker.choose(&mut rng), Some(2));
+    breaker.weights = vec![1.0];
+    assert_eq!(breaker.choose(&mut rng), Some(0));
+}
+
+#[cfg(feature = "rand_core")]
+#[test]
+pub fn test_rand_core() {
+    use rand_core::{RngCore, SeedableRng};
+
+    let mut rng: SquaresRNG = SquaresRNG::new_with_key(0, SquaresRNG::TEST_KEY);
+    let mut native: SquaresRNG = SquaresRNG::new_with_key(0, SquaresRNG::TEST_KEY);
+
+    // The trait methods walk the counter exactly like the native methods
+    for _ in 0..100 {
+        assert_eq!(rng.next_u32(), native.rand_u32());
+        assert_eq!(rng.next_u64(), native.rand_u64());
+        assert_eq!(rng.counter, native.counter);
+    }
+
+    // Lengths that aren't a multiple of 8 fill the tail from one more output
+    for length in [0, 1, 7, 8, 9, 15, 16, 21] {
+        let mut filled: SquaresRNG = SquaresRNG::new_with_key(0, SquaresRNG::TEST_KEY);
+        let mut manual: SquaresRNG = SquaresRNG::new_with_key(0, SquaresRNG::TEST_KEY);
+
+        let mut bytes: Vec<u8> = vec![0; length];
+        filled.fill_bytes(&mut bytes);
+
+        let mut expected: Vec<u8> = Vec::new();
+        while expected.len() < length {
+            expected.extend_from_slice(&manual.next_u64().to_le_bytes());
+        }
+        expected.truncate(length);
+
+        assert_eq!(bytes, expected);
+        assert_eq!(filled.counter, manual.counter);
+    }
+
+    let mut seed: [u8; 16] = [0; 16];
+    seed[..8].copy_from_slice(&SquaresRNG::TEST_KEY.to_le_bytes());
+    seed[8..].copy_from_slice(&42u64.to_le_bytes());
+    let seeded: SquaresRNG = SquaresRNG::from_seed(seed);
+    assert_eq!((seeded.key, seeded.counter), (SquaresRNG::TEST_KEY, 42));
+
+    let first: SquaresRNG = SquaresRNG::seed_from_u64(7);
+    let second: SquaresRNG = SquaresRNG::seed_from_u64(7);
+    assert_eq!(first.key, second.key);
+    assert_ne!(first.key, SquaresRNG::seed_from_u64(8).key);
+    assert!((28..=36).contains(&first.key.count_ones()));
+}
+
+#[test]
+pub fn test_random_intervals() {
+    let mut rng: SquaresRNG = SquaresRNG::new_with_key(0, SquaresRNG::TEST_KEY);
+
+    for _ in 0..200 {
+        let intervals: Vec<(f64, f64)> = rng.random_intervals(60.0, 8, 2.0, 6.0, 200);
+        assert!(!intervals.is_empty() && intervals.len() <= 8);
+
+        for (start, end) in intervals.iter() {
+            assert!(*start >= 0.0 && *end <= 60.0);
+            assert!(end - start >= 2.0 - 1e-9 && end - start <= 6.0 + 1e-9);
+        }
+        assert!(intervals.windows(2).all(|pair| pair[0].1 <= pair[1].0));
+    }
+
+    assert!(rng.random_intervals(1.0, 4, 2.0, 3.0, 100).is_empty());
+    assert!(rng.random_intervals(10.0, 4, 3.0, 2.0, 100).is_empty());
+}
+
+#[test]
+pub fn test_rand_u64_counter_walk() {
+    let mut rng: SquaresRNG = SquaresRNG::new_with_key(0, SquaresRNG::TEST_KEY);
+    let mut narrow: SquaresRNG = SquaresRNG::new_with_key(0, SquaresRNG::TEST_KEY);
+
+    let outputs: Vec<u32> = (0..1000).map(|_| narrow.rand_u32()).collect();
+    assert_eq!(narrow.counter, 1000);
+
+    for (counter, output) in outputs.iter().enumerate() {
+        assert_eq!(SquaresRNG::new_with_key(counter as u64, SquaresRNG::TEST_KEY).rand_u32(), *output);
+    }
+
+    // rand_u64 joins two rand_u32 outputs, the first in the high bits
+    let wide: Vec<u64> = (0..500).map(|_| rng.rand_u64()).collect();
+    assert_eq!(rng.counter, 1000);
+    for (i, value) in wide.iter().enumerate() {
+        assert_eq!(*value, ((outputs[i * 2] as u64) << 32) | outputs[i * 2 + 1] as u64);
+    }
+    assert!(wide.iter().any(|value| *value >= 1 << 32));
+}
+
+#[test]
+pub fn test_importance_sample() {
+    let mut rng: SquaresRNG = SquaresRNG::new_with_key(0, SquaresRNG::TEST_KEY);
+
+    // The integral of x * x^2 from 0 to 2 is 4
+    let target = |x: f64| x * x;
+    let f = |x: f64| x;
+
+    let count: u64 = 1_000_000;
+    let mut normalized: f64 = 0.0;
+    let mut fallback: f64 = 0.0;
+
+    for _ in 0..count {
+        let (x, weight) = rng.importance_sample(0.0, 2.0, target, 0.5);
+        assert!((0.0..=2.0).contains(&x));
+        normalized += weight * f(x);
+
+        let (x, weight) = rng.importance_sample(0.0, 2.0, target, 0.0);
+        fallback += weight * f(x);
+    }
+
+    let normalized: f64 = normalized / count as f64;
+    let fallback: f64 = fallback / count as f64;
+    println!("\nRNG Importance Sample Integral (Closer to 4.0): {}, {}\n", normalized, fallback);
+
+    assert!((normalized - 4.0).abs() < 0.02);
+    assert!((fallback - 4.0).abs() < 0.02);
+}
+
+#[test]
+pub fn test_rand_index_uniformality() {
+    let mut rng: SquaresRNG = SquaresRNG::new_with_key(0, SquaresRNG::TEST_KEY);
+
+    let count: u64 = 7_000_000;
+    let mut buckets: [u64; 7] = [0; 7];
+
+    for _ in 0..count {
+        buckets[rng.rand_index(7)] += 1;
+    }
+
+    println!("\nRNG rand_index Buckets (Closer to 1000000 each): {:?}\n", buckets);
+    assert!(buckets.iter().all(|b| *b < 1_004_000 && *b > 996_000));
+
+    // Sizes above 2^32 can still reach the top of the range
+    let size: u64 = 3 << 32;
+    let mut upper: u64 = 0;
+    for _ in 0..30_000 {
+        let index: usize = rng.rand_index(size);
+        assert!((index as u64) < size);
+        if index as u64 >= 2 << 32 {
+            upper += 1;
+        }
+    }
+    assert!(upper < 10_500 && upper > 9_500);
+
+    assert_eq!(rng.rand_index(0), 0);
+    assert_eq!(rng.rand_index(1), 0);
+}
+
+#[test]
+pub fn test_random_latin_square() {
+    let mut rng: SquaresRNG = SquaresRNG::new_with_key(0, SquaresRNG::TEST_KEY);
+
+    let is_permutation = |values: Vec<usize>, n: usize| -> bool {
+        let mut sorted: Vec<usize> = values;
+        sorted.sort_unstable();
+        sorted == (0..n).collect::<Vec<usize>>()
+    };
+
+    for n in [1, 2, 5, 9] {
+        let square: Vec<Vec<usize>> = rng.random_latin_square(n);
+        assert_eq!(square.len(), n);
+
+        for i in 0..n {
+            assert!(is_permutation(square[i].clone(), n));
+            assert!(is_permutation(square.iter().map(|row| row[i]).collect(), n));
+        }
+    }
+
+    let first: Vec<Vec<usize>> = SquaresRNG::new_with_key(0, SquaresRNG::TEST_KEY).random_latin_square(6);
+    let second: Vec<Vec<usize>> = SquaresRNG::new_with_key(1000, SquaresRNG::TEST_KEY).random_latin_square(6);
+    assert_ne!(first, second);
+    assert!(rng.random_latin_square(0).is_empty());
+}
+
+#[test]
+pub fn test_counter_wraps() {
+    let mut rng: SquaresRNG = SquaresRNG::new_with_key(u64::MAX, SquaresRNG::TEST_KEY);
+
+    rng.rand_u32();
+    assert_eq!(rng.counter, 0);
+
+    let wrapped: u32 = rng.rand_u32();
+    assert_eq!(rng.counter, 1);
+    assert_eq!(wrapped, SquaresRNG::new_with_key(0, SquaresRNG::TEST_KEY).rand_u32());
+}
+
+#[test]
+pub fn test_jackknife_sample() {
+    let mut rng: SquaresRNG = SquaresRNG::new_with_key(0, SquaresRNG::TEST_KEY);
+
+    let values: [u32; 8] = [10, 11, 12, 13, 14, 15, 16, 17];
+    let mut left_out: [u64; 8] = [0; 8];
+
+    for _ in 0..80_000 {
+        let (index, rest) = rng.jackknife_sample(&values);
+        assert_eq!(rest.len(), values.len() - 1);
+        assert!(!rest.contains(&&values[index]));
+        assert!(values.iter().enumerate().filter(|(i, _)| *i != index).all(|(_, value)| rest.contains(&value)));
+        left_out[index] += 1;
+    }
+
+    println!("\nRNG Jackknife Left Out (Closer to 10000 each): {:?}\n", left_out);
+    assert!(left_out.iter().all(|l| *l < 10_500 && *l > 9_500));
+
+    let empty: [u32; 0] = [];
+    assert_eq!(rng.jackknife_sample(&empty), (0, Vec::new()));
+}
+
+#[test]
+pub fn test_rangei32_uniformality() {
+    let mut rng: SquaresRNG = SquaresRNG::new_with_key(0, SquaresRNG::TEST_KEY);
+
+    let count: u64 = 6_000_000;
+    let mut faces: [u64; 6] = [0; 6];
+
+    for _ in 0..count {
+        let roll: i32 = rng.rangei32(1, 6);
+        assert!((1..=6).contains(&roll));
+        faces[roll as usize - 1] += 1;
+    }
+
+    println!("\nRNG rangei32 d6 (Closer to 1000000 each): {:?}\n", faces);
+    assert!(faces.iter().all(|f| *f < 1_004_000 && *f > 996_000));
+
+    assert_eq!(rng.rangei32(-4, -4), -4);
+    for _ in 0..1000 {
+        assert!((-3..=8).contains(&rng.rangei32(8, -3)));
+    }
+
+    let mut negative: bool = false;
+    let mut positive: bool = false;
+    for _ in 0..1000 {
+        let value: i32 = rng.rangei32(i32::MIN, i32::MAX);
+        negative |= value < 0;
+        positive |= value > 0;
+    }
+    assert!(negative && positive);
+}
+
+#[test]
+pub fn test_rangei64_uniformality() {
+    let mut rng: SquaresRNG = SquaresRNG::new_with_key(0, SquaresRNG::TEST_KEY);
+
+    let count: u64 = 6_000_000;
+    let mut faces: [u64; 6] = [0; 6];
+
+    for _ in 0..count {
+        let roll: i64 = rng.rangei64(-3, 2);
+        assert!((-3..=2).contains(&roll));
+        faces[(roll + 3) as usize] += 1;
+    }
+
+    println!("\nRNG rangei64 (Closer to 1000000 each): {:?}\n", faces);
+    assert!(faces.iter().all(|f| *f < 1_004_000 && *f > 996_000));
+
+    assert_eq!(rng.rangei64(i64::MAX, i64::MAX), i64::MAX);
+    for _ in 0..1000 {
+        assert!((i64::MAX - 1..=i64::MAX).contains(&rng.rangei64(i64::MAX, i64::MAX - 1)));
+        assert!((i64::MIN..=i64::MIN + 1).contains(&rng.rangei64(i64::MIN, i64::MIN + 1)));
+    }
+
+    let mut negative: bool = false;
+    let mut large: bool = false;
+    for _ in 0..1000 {
+        let value: i64 = rng.rangei64(i64::MIN, i64::MAX);
+        negative |= value < 0;
+        large |= value > i32::MAX as i64;
+    }
+    assert!(negative && large);
+}
+
+#[test]
+pub fn test_constrained_bools() {
+    let mut rng: SquaresRNG = SquaresRNG::new_with_key(0, SquaresRNG::TEST_KEY);
+
+    let mut counts: [u64; 11] = [0; 11];
+    let mut positions: [u64; 10] = [0; 10];
+
+    for _ in 0..100_000 {
+        let rolls: Vec<bool> = rng.constrained_bools(10, 3, 1);
+        assert_eq!(rolls.len(), 10);
+
+        let successes: usize = rolls.iter().filter(|roll| **roll).count();
+        assert!((2..=4).contains(&successes));
+        counts[successes] += 1;
+
+        for (position, roll) in rolls.iter().enumerate() {
+            if *roll {
+                positions[position] += 1;
+            }
+        }
+    }
+
+    println!("\nRNG Constrained Bools (Counts within 2..=4, positions closer to 30000 each): {:?}, {:?}\n", counts, positions);
+
+    assert!(counts[2] > 0 && counts[3] > 0 && counts[4] > 0);
+    assert!(positions.iter().all(|p| *p < 31_500 && *p > 28_500));
+    assert_eq!(rng.constrained_bools(5, 5, 0), vec![true; 5]);
+    assert_eq!(rng.constrained_bools(5, 0, 0), vec![false; 5]);
+}
+
+#[test]
+pub fn test_softmax_sample() {
+    let mut rng: SquaresRNG = SquaresRNG::new_with_key(0, SquaresRNG::TEST_KEY);
+
+    let actions: [f64; 4] = [1.0, 2.0, 3.0, 10.0];
+    let score = |action: &f64| *action;
+
+    let count: u64 = 100_000;
+    let mut best_shares: Vec<f64> = Vec::new();
+
+    for temperature in [100.0, 10.0, 1.0, 0.1] {
+        let mut best: u64 = 0;
+        for _ in 0..count {
+            if *rng.softmax_sample(&actions, temperature, score).unwrap() == 10.0 {
+                best += 1;
+            }
+        }
+        best_shares.push(best as f64 / count as f64);
+    }
+
+    println!("\nRNG Softmax Best Share (Increasing as temperature drops): {:?}\n", best_shares);
+
+    assert!(best_shares[0] < 0.3);
+    assert!(best_shares.windows(2).all(|pair| pair[0] < pair[1]));
+
+    // At a very low temperature a clearly worse option is never picked
+    for _ in 0..count {
+        assert_eq!(*rng.softmax_sample(&actions, 0.05, score).unwrap(), 10.0);
+        assert_eq!(*rng.softmax_sample(&actions, 0.0, score).unwrap(), 10.0);
+    }
+
+    let empty: [f64; 0] = [];
+    assert_eq!(rng.softmax_sample(&empty, 1.0, score), None);
+}
+
+#[test]
+pub fn test_shuffle() {
+    let mut rng: SquaresRNG = SquaresRNG::new_with_key(0, SquaresRNG::TEST_KEY);
+
+    let count: u64 = 72_000;
+    let mut permutations: std::collections::HashMap<[u8; 6], u64> = std::collections::HashMap::new();
+
+    for _ in 0..count {
+        let mut values: [u8; 6] = [0, 1, 2, 3, 4, 5];
+        rng.shuffle(&mut values);
+        *permutations.entry(values).or_insert(0) += 1;
+    }
+
+    let fewest: u64 = *permutations.values().min().unwrap();
+    let most: u64 = *permutations.values().max().unwrap();
+    println!("\nRNG Shuffle Permutations (720, closer to 100 each): {}, fewest: {}, most: {}\n", permutations.len(), fewest, most);
+
+    assert_eq!(permutations.len(), 720);
+    assert!(fewest > 50 && most < 150);
+
+    let mut empty: [u8; 0] = [];
+    let mut single: [u8; 1] = [9];
+    let counter: u64 = rng.counter;
+    rng.shuffle(&mut empty);
+    rng.shuffle(&mut single);
+    assert_eq!(single, [9]);
+    assert_eq!(rng.counter, counter);
+}
+
+#[test]
+pub fn test_choose() {
+    let mut rng: SquaresRNG = SquaresRNG::new_with_key(0, SquaresRNG::TEST_KEY);
+
+    let empty: [u32; 0] = [];
+    assert_eq!(rng.choose(&empty), None);
+    assert_eq!(rng.choose(&[42]), Some(&42));
+
+    let values: [usize; 5] = [0, 1, 2, 3, 4];
+    let count: u64 = 1_000_000;
+    let mut picks: [u64; 5] = [0; 5];
+
+    for _ in 0..count {
+        picks[*rng.choose(&values).unwrap()] += 1;
+    }
+
+    println!("\nRNG Choose (Closer to 200000 each): {:?}\n", picks);
+    assert!(picks.iter().all(|p| *p < 202_000 && *p > 198_000));
+}
+
+#[test]
+pub fn test_choose_mut() {
+    let mut rng: SquaresRNG = SquaresRNG::new_with_key(0, SquaresRNG::TEST_KEY);
+
+    let mut empty: [u64; 0] = [];
+    assert_eq!(rng.choose_mut(&mut empty), None);
+
+    let mut single: [u64; 1] = [0];
+    *rng.choose_mut(&mut single).unwrap() += 1;
+    assert_eq!(single, [1]);
+
+    // Count the picks in the elements themselves
+    let count: u64 = 1_000_000;
+    let mut picks: [u64; 5] = [0; 5];
+    for _ in 0..count {
+        *rng.choose_mut(&mut picks).unwrap() += 1;
+    }
+
+    println!("\nRNG Choose Mut (Closer to 200000 each): {:?}\n", picks);
+    assert_eq!(picks.iter().sum::<u64>(), count);
+    assert!(picks.iter().all(|p| *p < 202_000 && *p > 198_000));
+}
+
+#[test]
+pub fn test_sample_with_replacement() {
+    let mut rng: SquaresRNG = SquaresRNG::new_with_key(0, SquaresRNG::TEST_KEY);
+
+    let values: [u32; 6] = [0, 1, 2, 3, 4, 5];
+    let mut seen: [u64; 6] = [0; 6];
+    let mut repeated: bool = false;
+
+    for _ in 0..10_000 {
+        let sample: Vec<&u32> = rng.sample_with_replacement(&values, 4);
+        assert_eq!(sample.len(), 4);
+
+        for value in sample.iter() {
+            assert!(values.iter().any(|v| core::ptr::eq(v, *value)));
+            seen[**value as usize] += 1;
+        }
+        repeated |= (1..sample.len()).any(|i| sample[..i].contains(&sample[i]));
+    }
+
+    println!("\nRNG Sample With Replacement (Closer to 6667 each): {:?}\n", seen);
+
+    assert!(repeated);
+    assert!(seen.iter().all(|s| *s < 7_100 && *s > 6_200));
+    assert_eq!(rng.sample_with_replacement(&values, 0).len(), 0);
+
+    let empty: [u32; 0] = [];
+    assert!(rng.sample_with_replacement(&empty, 3).is_empty());
+}
+
+#[test]
+pub fn test_randf32_normal() {
+    let mut rng: SquaresRNG = SquaresRNG::new_with_key(0, SquaresRNG::TEST_KEY);
+
+    let count: u64 = 4_000_000;
+    let mut sum: f64 = 0.0;
+    let mut squares: f64 = 0.0;
+
+    for _ in 0..count {
+        let value: f64 = rng.randf32_normal(10.0, 2.5) as f64;
+        sum += value;
+        squares += value * value;
+    }
+
+    let mean: f64 = sum / count as f64;
+    let deviation: f64 = (squares / count as f64 - mean * mean).sqrt();
+    println!("\nRNG f32 Normal (Closer to 10.0 and 2.5): mean: {}, deviation: {}\n", mean, deviation);
+
+    assert!((mean - 10.0).abs() < 0.005);
+    assert!((deviation - 2.5).abs() < 0.005);
+
+    // Each value takes two counter steps
+    assert_eq!(rng.counter, count * 2);
+}
+
+#[test]
+pub fn test_randf64_normal() {
+    let mut rng: SquaresRNG = SquaresRNG::new_with_key(0, SquaresRNG::TEST_KEY);
+
+    let count: u64 = 4_000_000;
+    let mut sum: f64 = 0.0;
+    let mut squares: f64 = 0.0;
+    let mut within_one: u64 = 0;
+
+    for _ in 0..count {
+        let value: f64 = rng.randf64_normal(-3.0, 0.5);
+        sum += value;
+        squares += value * value;
+        if (value + 3.0).abs() < 0.5 {
+            within_one += 1;
+        }
+    }
+
+    let mean: f64 = sum / count as f64;
+    let deviation: f64 = (squares / count as f64 - mean * mean).sqrt();
+    let within_one: f64 = within_one as f64 / count as f64;
+    println!("\nRNG f64 Normal (Closer to -3.0, 0.5, and 0.6827): mean: {}, deviation: {}, within one deviation: {}\n", mean, deviation, within_one);
+
+    assert!((mean + 3.0).abs() < 0.001);
+    assert!((deviation - 0.5).abs() < 0.001);
+    assert!((within_one - 0.6827).abs() < 0.002);
+}
+
+#[test]
+pub fn test_traverse_weighted_tree() {
+    let mut rng: SquaresRNG = SquaresRNG::new_with_key(0, SquaresRNG::TEST_KEY);
+
+    // 0 -> 1 (0.3), 2 (0.7), and 1 -> 3 (1.0), 4 (3.0)
+    let tree: WeightedTree = WeightedTree::new(vec![
+        vec![(1, 0.3), (2, 0.7)],
+        vec![(3, 1.0), (4, 3.0)],
+        vec![],
+        vec![],
+        vec![],
+    ]);
+
+    let count: u64 = 1_000_000;
+    let mut leaves: [u64; 5] = [0; 5];
+
+    for _ in 0..count {
+        let path: Vec<usize> = rng.traverse_weighted_tree(&tree);
+        assert_eq!(path[0], 0);
+        assert!(path.windows(2).all(|step| tree.children[step[0]].iter().any(|(child, _)| *child == step[1])));
+
+        let leaf: usize = *path.last().unwrap();
+        assert!(tree.children[leaf].is_empty());
+        leaves[leaf] += 1;
+    }
+
+    let leaves: Vec<f64> = leaves.iter().map(|l| *l as f64 / count as f64).collect();
+    println!("\nRNG Weighted Tree Leaves (Closer to 0.7, 0.075, 0.225): {}, {}, {}\n", leaves[2], leaves[3], leaves[4]);
+
+    assert!((leaves[2] - 0.7).abs() < 0.003);
+    assert!((leaves[3] - 0.075).abs() < 0.002);
+    assert!((leaves[4] - 0.225).abs() < 0.003);
+    assert!(rng.traverse_weighted_tree(&WeightedTree::new(Vec::new())).is_empty());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+pub fn test_serde_round_trip() {
+    let mut rng: SquaresRNG = SquaresRNG::new_with_key(0, SquaresRNG::TEST_KEY);
+    for _ in 0..37 {
+        rng.randf32();
+    }
+
+    let saved: String = serde_json::to_string(&rng).unwrap();
+    let mut loaded: SquaresRNG = serde_json::from_str(&saved).unwrap();
+
+    assert_eq!((loaded.key, loaded.counter), (rng.key, rng.counter));
+    for _ in 0..100 {
+        assert_eq!(loaded.randf64_normal(0.0, 1.0), rng.randf64_normal(0.0, 1.0));
+        assert_eq!(loaded.rand_u64(), rng.rand_u64());
+    }
+
+    // Saves that only have the key and counter still load
+    let minimal: SquaresRNG = serde_json::from_str("{\"key\": 2623288866036836561, \"counter\": 5}").unwrap();
+    assert_eq!((minimal.key, minimal.counter), (SquaresRNG::TEST_KEY, 5));
+}
+
+#[test]
+pub fn test_feistel_permute() {
+    let mut rng: SquaresRNG = SquaresRNG::new_with_key(0, SquaresRNG::TEST_KEY);
+
+    for n in [2, 3, 10, 100, 1000, 4096, 5000] {
+        let mapped: Vec<u64> = (0..n).map(|index| rng.feistel_permute(n, index)).collect();
+
+        let mut sorted: Vec<u64> = mapped.clone();
+        sorted.sort_unstable();
+        assert_eq!(sorted, (0..n).collect::<Vec<u64>>());
+
+        if n >= 10 {
+            assert_ne!(mapped, (0..n).collect::<Vec<u64>>());
+        }
+
+        let again: Vec<u64> = (0..n).map(|index| rng.feistel_permute(n, index)).collect();
+        assert_eq!(mapped, again);
+    }
+
+    let mut other: SquaresRNG = SquaresRNG::new_with_key(1, SquaresRNG::TEST_KEY);
+    let first: Vec<u64> = (0..100).map(|index| rng.feistel_permute(100, index)).collect();
+    let second: Vec<u64> = (0..100).map(|index| other.feistel_permute(100, index)).collect();
+    assert_ne!(first, second);
+    assert_eq!(rng.counter, 0);
+
+    let huge: u64 = u64::MAX - 12345;
+    assert!(rng.feistel_permute(huge, huge - 1) < huge);
+}
+
+#[cfg(all(not(feature = "std"), feature = "libm"))]
+#[test]
+pub fn test_no_std_float() {
+    // Tests always link std, which would shadow the libm versions, so call them through the trait directly
+    assert!((<f64 as Float>::sqrt(2.0) - core::f64::consts::SQRT_2).abs() < 1e-12);
+    assert!((<f64 as Float>::ln(core::f64::consts::E) - 1.0).abs() < 1e-12);
+    assert!((<f64 as Float>::exp(1.0) - core::f64::consts::E).abs() < 1e-12);
+    assert!((<f64 as Float>::powf(2.0, 10.0) - 1024.0).abs() < 1e-9);
+    assert!((<f64 as Float>::powi(3.0, -2) - 1.0 / 9.0).abs() < 1e-12);
+    assert!((<f64 as Float>::ln_1p(1e-10) - 1e-10).abs() < 1e-20);
+    assert!((<f64 as Float>::exp_m1(1e-10) - 1e-10).abs() < 1e-20);
+    assert!((<f64 as Float>::hypot(3.0, 4.0) - 5.0).abs() < 1e-12);
+    assert_eq!(<f64 as Float>::floor(-1.5), -2.0);
+    assert_eq!(<f64 as Float>::ceil(-1.5), -1.0);
+    assert_eq!(<f64 as Float>::round(2.5), 3.0);
+    assert!((<f64 as Float>::fract(-1.25) + 0.25).abs() < 1e-12);
+    assert!((<f64 as Float>::rem_euclid(-1.0, 3.0) - 2.0).abs() < 1e-12);
+
+    let (sin, cos) = <f32 as Float>::sin_cos(core::f32::consts::FRAC_PI_2);
+    assert!((sin - 1.0).abs() < 1e-6 && cos.abs() < 1e-6);
+    assert!((<f32 as Float>::sin(core::f32::consts::PI)).abs() < 1e-6);
+    assert!((<f32 as Float>::cos(0.0) - 1.0).abs() < 1e-6);
+    assert!((<f32 as Float>::sqrt(16.0) - 4.0).abs() < 1e-6);
+    assert!((<f32 as Float>::rem_euclid(-0.5, 2.0) - 1.5).abs() < 1e-6);
+
+    // The generator's own float paths go through the same functions when std is off
+    let mut rng: SquaresRNG = SquaresRNG::new_with_key(0, SquaresRNG::TEST_KEY);
+    let value: f64 = rng.randf64();
+    assert!((0.0..1.0).contains(&value));
+    let normal: f64 = rng.randf64_normal(0.0, 1.0);
+    assert!(normal.is_finite());
+}
+
+#[cfg(feature = "keys_table")]
+#[test]
+pub fn test_keys_table() {
+    let rng: SquaresRNG = SquaresRNG::new(5, 0);
+    assert_eq!(rng.key, SquaresRNG::TEST_KEY);
+    assert_eq!(rng.counter, 5);
+
+    // Key indices wrap around the table
+    assert_eq!(SquaresRNG::new(0, 8192).key, SquaresRNG::TEST_KEY);
+    assert_eq!(SquaresRNG::new(0, 1).key, 0x4a8dfb965c72184f);
+}
+
+#[test]
+pub fn test_triangular_f64() {
+    let mut rng: SquaresRNG = SquaresRNG::new_with_key(0, SquaresRNG::TEST_KEY);
+
+    let count: u64 = 1_000_000;
+    let mut sum: f64 = 0.0;
+    let mut below_mode: u64 = 0;
+    for _ in 0..count {
+        let value: f64 = rng.triangular_f64(1.0, 5.0, 2.0);
+        assert!((1.0..=5.0).contains(&value));
+        sum += value;
+        if value < 2.0 {
+            below_mode += 1;
+        }
+    }
+
+    let mean: f64 = sum / count as f64;
+    let below_mode: f64 = below_mode as f64 / count as f64;
+    println!("\nRNG Triangular (Closer to 2.6667 and 0.25): mean: {}, below mode: {}\n", mean, below_mode);
+
+    assert!((mean - 8.0 / 3.0).abs() < 0.01);
+    assert!((below_mode - 0.25).abs() < 0.005);
+    assert!(rng.triangular_f64(1.0, 5.0, 6.0).is_nan());
+    assert_eq!(rng.triangular_f64(3.0, 3.0, 3.0), 3.0);
+}
+
+#[test]
+pub fn test_exponential_f64() {
+    let mut rng: SquaresRNG = SquaresRNG::new_with_key(0, SquaresRNG::TEST_KEY);
+
+    let count: u64 = 1_000_000;
+    let mut sum: f64 = 0.0;
+    for _ in 0..count {
+        let value: f64 = rng.exponential_f64(4.0);
+        assert!(value >= 0.0 && value.is_finite());
+        sum += value;
+    }
+
+    let mean: f64 = sum / count as f64;
+    println!("\nRNG Exponential (Closer to 0.25): {}\n", mean);
+
+    assert!((mean - 0.25).abs() < 0.005);
+    assert!(rng.exponential_f64(0.0).is_nan());
+    assert!(rng.exponential_f64(-1.0).is_nan());
+}
+
+#[test]
+pub fn test_sample_distribution() {
+    let dists: [Distribution; 4] = [
+        Distribution::Uniform { min: -2.0, max: 8.0 },
+        Distribution::Normal { mean: 10.0, stddev: 3.0 },
+        Distribution::Triangular { min: 0.0, max: 1.0, mode: 0.9 },
+        Distribution::Exponential { lambda: 0.5 },
+    ];
+
+    for dist in dists.iter() {
+        let mut rng: SquaresRNG = SquaresRNG::new_with_key(0, SquaresRNG::TEST_KEY);
+        let mut direct: SquaresRNG = SquaresRNG::new_with_key(0, SquaresRNG::TEST_KEY);
+
+        for _ in 0..1000 {
+            let expected: f64 = match *dist {
+                Distribution::Uniform { min, max } => direct.rangef64(min, max),
+                Distribution::Normal { mean, stddev } => direct.randf64_normal(mean, stddev),
+                Distribution::Triangular { min, max, mode } => direct.triangular_f64(min, max, mode),
+                Distribution::Exponential { lambda } => direct.exponential_f64(lambda),
+            };
+            assert_eq!(rng.sample(dist), expected);
+        }
+        assert_eq!(rng.counter, direct.counter);
+    }
+}
+
+#[test]
+pub fn test_validate_key() {
+    let good_keys: [u64; 4] = [SquaresRNG::TEST_KEY, 0x4a8dfb965c72184f, 0x87b2ad565c7426ab, 0x8a64d3efe5adf7b1];
+    for key in good_keys {
+        assert!(SquaresRNG::validate_key(key));
+        let rng: SquaresRNG = SquaresRNG::new_with_key_checked(7, key).unwrap();
+        assert_eq!((rng.counter, rng.key), (7, key));
+    }
+
+    assert!(!SquaresRNG::validate_key(0));
+    assert!(!SquaresRNG::validate_key(u64::MAX));
+    assert!(!SquaresRNG::validate_key(0xFFFFFFFF00000000));
+    assert!(!SquaresRNG::validate_key(0x0F0F0F0F0F0F0F0F));
+    assert!(!SquaresRNG::validate_key(0xAAAAAAAAAAAAAAAA));
+
+    assert_eq!(SquaresRNG::new_with_key_checked(0, 0).err(), Some(KeyError::Unbalanced(0)));
+    assert_eq!(SquaresRNG::new_with_key_checked(0, u64::MAX).err(), Some(KeyError::Unbalanced(64)));
+    assert_eq!(SquaresRNG::new_with_key_checked(0, 0xFFFFFFFF00000000).err(), Some(KeyError::Patterned));
+
+    #[cfg(feature = "keys_table")]
+    assert!(KEYS_TABLE.iter().all(|&key| SquaresRNG::validate_key(key)));
+
+    // Keys made from seeds should always pass
+    for seed in 0..10_000 {
+        assert!(SquaresRNG::validate_key(SquaresRNG::derive_key(seed)));
+    }
+}
+
+#[test]
+pub fn test_bsp_split() {
+    let mut rng: SquaresRNG = SquaresRNG::new_with_key(0, SquaresRNG::TEST_KEY);
+
+    for _ in 0..200 {
+        let (width, height, min_leaf): (u32, u32, u32) = (40 + rng.rand_index(60) as u32, 30 + rng.rand_index(40) as u32, 4 + rng.rand_index(6) as u32);
+        let leaves: Vec<(u32, u32, u32, u32)> = rng.bsp_split(width, height, min_leaf, 6);
+        assert!(leaves.len() > 1 && leaves.len() <= 64);
+
+        let mut covered: Vec<u8> = vec![0; (width * height) as usize];
+        for &(x, y, w, h) in leaves.iter() {
+            assert!(w >= min_leaf && h >= min_leaf);
+            assert!(x + w <= width && y + h <= height);
+            for cy in y..y + h {
+                for cx in x..x + w {
+                    covered[(cy * width + cx) as usize] += 1;
+                }
+            }
+        }
+
+        // Every cell in exactly one leaf means no overlaps and no gaps
+        assert!(covered.iter().all(|&count| count == 1));
+    }
+
+    let leaves: Vec<(u32, u32, u32, u32)> = rng.bsp_split(64, 64, 8, 20);
+    println!("\nRNG BSP Split (Closer to many leaves): {}\n", leaves.len());
+    assert!(leaves.iter().all(|&(_, _, w, h)| w < 16 && h < 16));
+    assert!(leaves.iter().all(|&(_, _, w, h)| w >= 8 && h >= 8));
+
+    assert_eq!(rng.bsp_split(64, 64, 8, 0), vec![(0, 0, 64, 64)]);
+    assert!(rng.bsp_split(5, 64, 8, 4).is_empty());
+
+    // Huge leaves near the u32 limit
+    let half: u32 = u32::MAX / 2;
+    assert_eq!(rng.bsp_split(u32::MAX, u32::MAX, half + 1, 4), vec![(0, 0, u32::MAX, u32::MAX)]);
+    let leaves: Vec<(u32, u32, u32, u32)> = rng.bsp_split(u32::MAX, half + 1, half, 4);
+    assert_eq!(leaves.len(), 2);
+    assert!(leaves.iter().all(|&(_, _, w, h)| w >= half && h == half + 1));
+    assert_eq!(leaves[0].2 as u64 + leaves[1].2 as u64, u32::MAX as u64);
+}
+
+#[test]
+pub fn test_jump_ahead_and_back() {
+    let mut rng: SquaresRNG = SquaresRNG::new_with_key(0, SquaresRNG::TEST_KEY);
+    rng.jump_ahead(1000);
+    assert_eq!(rng.position(), 1000);
+
+    let first: Vec<u32> = (0..50).map(|_| rng.rand_u32()).collect();
+    rng.jump_back(50);
+    let again: Vec<u32> = (0..50).map(|_| rng.rand_u32()).collect();
+    assert_eq!(first, again);
+
+    let mut skipped: SquaresRNG = SquaresRNG::new_with_key(0, SquaresRNG::TEST_KEY);
+    skipped.jump_ahead(1025);
+    assert_eq!(skipped.rand_u32(), first[25]);
+
+    // Normal values only depend on the position, even when it is set directly
+    let saved: u64 = rng.position();
+    let normal: f64 = rng.randf64_normal(0.0, 1.0);
+    rng.set_position(saved);
+    assert_eq!(rng.randf64_normal(0.0, 1.0), normal);
+    let mut literal: SquaresRNG = SquaresRNG { key: SquaresRNG::TEST_KEY, counter: saved };
+    assert_eq!(literal.randf64_normal(0.0, 1.0), normal);
+    rng.counter = saved;
+    assert_eq!(rng.randf64_normal(0.0, 1.0), normal);
+
+    rng.set_position(3);
+    rng.jump_back(5);
+    assert_eq!(rng.position(), u64::MAX - 1);
+    rng.jump_ahead(2);
+    assert_eq!(rng.position(), 0);
+}
+
+#[test]
+pub fn test_jittered_snap_angle() {
+    let mut rng: SquaresRNG = SquaresRNG::new_with_key(0, SquaresRNG::TEST_KEY);
+
+    for (snap, jitter, steps) in [(90.0f32, 5.0f32, 4usize), (45.0, 2.0, 8), (100.0, 10.0, 4), (360.0, 0.0, 1)] {
+        let mut hits: Vec<u64> = vec![0; steps];
+        for _ in 0..100_000 {
+            let angle: f32 = rng.jittered_snap_angle(snap, jitter);
+            let nearest: f32 = (angle / snap).round();
+            assert!((angle - nearest * snap).abs() <= jitter + 0.001);
+            hits[nearest as usize] += 1;
+        }
+
+        println!("\nRNG Jittered Snap Angle (Closer to even counts): {:?}\n", hits);
+        assert!(hits.iter().all(|&count| count > 0));
+    }
+
+    assert!(rng.jittered_snap_angle(0.0, 5.0).is_nan());
+    assert!(rng.jittered_snap_angle(90.0, -1.0).is_nan());
+}
+
+#[test]
+pub fn test_unit_vec2f32() {
+    let mut rng: SquaresRNG = SquaresRNG::new_with_key(0, SquaresRNG::TEST_KEY);
+
+    let count: u64 = 1_000_000;
+    let mut sum: (f64, f64) = (0.0, 0.0);
+    let mut first_quadrant: u64 = 0;
+    for _ in 0..count {
+        let (x, y) = rng.unit_vec2f32();
+        assert!(((x * x + y * y).sqrt() - 1.0).abs() < 0.0001);
+        sum = (sum.0 + x as f64, sum.1 + y as f64);
+        if x > 0.0 && y > 0.0 {
+            first_quadrant += 1;
+        }
+    }
+
+    let mean: (f64, f64) = (sum.0 / count as f64, sum.1 / count as f64);
+    let first_quadrant: f64 = first_quadrant as f64 / count as f64;
+    println!("\nRNG Unit Vec2 (Closer to 0.0, 0.0, and 0.25): {:?}, {}\n", mean, first_quadrant);
+
+    assert!(mean.0.abs() < 0.005 && mean.1.abs() < 0.005);
+    assert!((first_quadrant - 0.25).abs() < 0.002);
+}
+
+#[test]
+pub fn test_unit_vec3f32() {
+    let mut rng: SquaresRNG = SquaresRNG::new_with_key(0, SquaresRNG::TEST_KEY);
+
+    let count: u64 = 1_000_000;
+    let mut sum: (f64, f64, f64) = (0.0, 0.0, 0.0);
+    let mut near_corner: u64 = 0;
+    let mut near_face: u64 = 0;
+    for _ in 0..count {
+        let (x, y, z) = rng.unit_vec3f32();
+        assert!(((x * x + y * y + z * z).sqrt() - 1.0).abs() < 0.0001);
+        sum = (sum.0 + x as f64, sum.1 + y as f64, sum.2 + z as f64);
+
+        // Equal sized caps around a cube corner direction and a cube face direction should get the same share
+        let corner: f32 = (x + y + z) / 3.0f32.sqrt();
+        if corner > 0.9 {
+            near_corner += 1;
+        }
+        if x > 0.9 {
+            near_face += 1;
+        }
+    }
+
+    let mean: (f64, f64, f64) = (sum.0 / count as f64, sum.1 / count as f64, sum.2 / count as f64);
+    let (near_corner, near_face): (f64, f64) = (near_corner as f64 / count as f64, near_face as f64 / count as f64);
+    println!("\nRNG Unit Vec3 (Closer to 0.0, 0.0, 0.0, and 0.05 twice): {:?}, {}, {}\n", mean, near_corner, near_face);
+
+    assert!(mean.0.abs() < 0.005 && mean.1.abs() < 0.005 && mean.2.abs() < 0.005);
+    assert!((near_corner - 0.05).abs() < 0.002);
+    assert!((near_face - 0.05).abs() < 0.002);
+}
+
+#[test]
+pub fn test_fatigue_chooser() {
+    let mut rng: SquaresRNG = SquaresRNG::new_with_key(0, SquaresRNG::TEST_KEY);
+
+    // After one pick out of two items, the other one should come next 2/3 of the time
+    let trials: u64 = 100_000;
+    let mut switched: u64 = 0;
+    for _ in 0..trials {
+        let mut chooser: FatigueChooser<char> = FatigueChooser::new(vec!['a', 'b'], 0.9);
+        let first: char = *chooser.choose(&mut rng);
+        if *chooser.choose(&mut rng) != first {
+            switched += 1;
+        }
+    }
+    let switched: f64 = switched as f64 / trials as f64;
+    println!("\nRNG Fatigue Chooser (Closer to 0.6667): {}\n", switched);
+    assert!((switched - 2.0 / 3.0).abs() < 0.01);
+
+    // Without decay, picks stay close to even because the most picked item is always the least likely
+    let mut chooser: FatigueChooser<usize> = FatigueChooser::new((0..5).collect(), 0.95);
+    let mut counts: [u64; 5] = [0; 5];
+    for _ in 0..10_000 {
+        counts[*chooser.choose(&mut rng)] += 1;
+    }
+    assert!(counts.iter().all(|&count| (1950..=2050).contains(&count)));
+
+    // Decay lets everything recover until the items are equally likely again
+    for _ in 0..1000 {
+        chooser.decay();
+    }
+    assert!(chooser.fatigue().iter().all(|&fatigue| fatigue < 1e-9));
+}
+
+#[test]
+pub fn test_fill_slices() {
+    let mut rng: SquaresRNG = SquaresRNG::new_with_key(0, SquaresRNG::TEST_KEY);
+    let mut single: SquaresRNG = SquaresRNG::new_with_key(0, SquaresRNG::TEST_KEY);
+
+    let mut buffer: [f32; 1000] = [0.0; 1000];
+    rng.fill_f32_slice(&mut buffer);
+    let average: f32 = buffer.iter().sum::<f32>() / buffer.len() as f32;
+    println!("\nRNG Fill f32 Slice (Closer to 0.5): {}\n", average);
+    assert!((average - 0.5).abs() < 0.03);
+    assert!(buffer.iter().all(|value| (0.0..1.0).contains(value)));
+    assert!(buffer.iter().all(|&value| value == single.randf32()));
+
+    let mut buffer: [f64; 1000] = [0.0; 1000];
+    rng.fill_f64_slice(&mut buffer);
+    let average: f64 = buffer.iter().sum::<f64>() / buffer.len() as f64;
+    assert!((average - 0.5).abs() < 0.03);
+    assert!(buffer.iter().all(|&value| value == single.randf64()));
+
+    let mut buffer: [f32; 1000] = [0.0; 1000];
+    rng.fill_range_f32_slice(&mut buffer, -4.0, 2.0);
+    let average: f32 = buffer.iter().sum::<f32>() / buffer.len() as f32;
+    assert!((average + 1.0).abs() < 0.2);
+    assert!(buffer.iter().all(|&value| value == single.rangef32(-4.0, 2.0)));
+
+    assert_eq!(rng.counter, 3000);
+    assert_eq!(rng.counter, single.counter);
+}
+
+#[test]
+pub fn test_random_color_temperature() {
+    let mut rng: SquaresRNG = SquaresRNG::new_with_key(0, SquaresRNG::TEST_KEY);
+
+    for _ in 0..10_000 {
+        let [r, _, b] = rng.random_color_temperature(1500.0, 3500.0);
+        assert!(r > b);
+        assert_eq!(r, 255);
+
+        let [r, _, b] = rng.random_color_temperature(9000.0, 20000.0);
+        assert!(b > r);
+        assert_eq!(b, 255);
+    }
+
+    let candle: [u8; 3] = rng.random_color_temperature(1900.0, 1900.0);
+    let daylight: [u8; 3] = rng.random_color_temperature(6500.0, 6500.0);
+    println!("\nRNG Color Temperature (Closer to orange and white): {:?}, {:?}\n", candle, daylight);
+    assert!(candle[0] > candle[1] && candle[1] > candle[2]);
+    assert!(daylight.iter().all(|&channel| channel > 240));
+
+    // Out of range temperatures clamp instead of producing garbage
+    assert_eq!(rng.random_color_temperature(0.0, 0.0), SquaresRNG::kelvin_to_rgb8(1000.0));
+    assert_eq!(rng.random_color_temperature(1e9, 1e9), SquaresRNG::kelvin_to_rgb8(40000.0));
+}
+
+#[test]
+pub fn test_iter_u64() {
+    let mut rng: SquaresRNG = SquaresRNG::new_with_key(0, SquaresRNG::TEST_KEY);
+    let mut direct: SquaresRNG = SquaresRNG::new_with_key(0, SquaresRNG::TEST_KEY);
+
+    let values: Vec<u64> = rng.iter_u64().take(10).collect();
+    let expected: Vec<u64> = (0..10).map(|_| direct.rand_u64()).collect();
+    assert_eq!(values, expected);
+    assert_eq!(rng.counter, 20);
+
+    let floats: Vec<f32> = rng.iter_f32().take(10).collect();
+    let expected: Vec<f32> = (0..10).map(|_| direct.randf32()).collect();
+    assert_eq!(floats, expected);
+
+    let even: usize = rng.iter_u64().take(100_000).filter(|value| value % 2 == 0).count();
+    println!("\nRNG Iter u64 (Closer to 50000): {}\n", even);
+    assert!((49_000..51_000).contains(&even));
+}
+
+#[test]
+pub fn test_quota_sample() {
+    let mut rng: SquaresRNG = SquaresRNG::new_with_key(0, SquaresRNG::TEST_KEY);
+
+    // (name, category) where 0 is ground, 1 is air, and 2 is boss
+    let enemies: [(&str, usize); 7] = [("slime", 0), ("wolf", 0), ("golem", 0), ("bat", 1), ("wasp", 1), ("dragon", 2), ("lich", 2)];
+    let quotas: [f64; 3] = [0.5, 0.3, 0.2];
+
+    for total in [10, 17, 100, 1] {
+        let picked: Vec<&(&str, usize)> = rng.quota_sample(&enemies, |enemy| enemy.1, &quotas, total);
+        assert_eq!(picked.len(), total);
+
+        let mut counts: [usize; 3] = [0; 3];
+        picked.iter().for_each(|enemy| counts[enemy.1] += 1);
+        for (count, quota) in counts.iter().zip(quotas) {
+            assert!((*count as f64 - quota * total as f64).abs() < 1.0);
+        }
+        if total == 10 {
+            println!("\nRNG Quota Sample (Closer to [5, 3, 2]): {:?}\n", counts);
+            assert_eq!(counts, [5, 3, 2]);
+        }
+    }
+
+    // Every member of a category gets picked
+    let picked: Vec<&(&str, usize)> = rng.quota_sample(&enemies, |enemy| enemy.1, &quotas, 1000);
+    assert!(enemies.iter().all(|enemy| picked.contains(&enemy)));
+
+    // A category with no members gives its share to the others
+    let picked: Vec<&(&str, usize)> = rng.quota_sample(&enemies[..5], |enemy| enemy.1, &quotas, 8);
+    assert_eq!(picked.iter().filter(|enemy| enemy.1 == 0).count(), 5);
+    assert_eq!(picked.iter().filter(|enemy| enemy.1 == 1).count(), 3);
+
+    assert!(rng.quota_sample(&enemies, |enemy| enemy.1, &[0.0, 0.0, 0.0], 10).is_empty());
+    assert!(rng.quota_sample(&enemies, |enemy| enemy.1, &[1.0, -1.0, 0.5], 10).is_empty());
+    assert!(rng.quota_sample(&enemies, |enemy| enemy.1, &quotas, 0).is_empty());
+}
+
+#[test]
+pub fn test_roll() {
+    let mut rng: SquaresRNG = SquaresRNG::new_with_key(0, SquaresRNG::TEST_KEY);
+
+    let mut faces: [u64; 6] = [0; 6];
+    for _ in 0..60_000 {
+        let value: i64 = rng.roll("1d6").unwrap();
+        faces[value as usize - 1] += 1;
+    }
+    println!("\nRNG Roll 1d6 (Closer to 10000 each): {:?}\n", faces);
+    assert!(faces.iter().all(|&count| (9_500..10_500).contains(&count)));
+
+    let mut sum: i64 = 0;
+    for _ in 0..100_000 {
+        let value: i64 = rng.roll("3d8+2").unwrap();
+        assert!((5..=26).contains(&value));
+        sum += value;
+    }
+    assert!((sum as f64 / 100_000.0 - 15.5).abs() < 0.05);
+
+    for _ in 0..1000 {
+        assert!((1..=20).contains(&rng.roll("d20").unwrap()));
+        assert!((-1..=2).contains(&rng.roll(" 1D4-2 ").unwrap()));
+    }
+
+    // Same rolls as doing it by hand
+    let mut direct: SquaresRNG = SquaresRNG::new_with_key(rng.counter, rng.key);
+    let expected: i64 = direct.rangei64(1, 10) + direct.rangei64(1, 10) + 7;
+    assert_eq!(rng.roll("2d10+7"), Ok(expected));
+
+    assert_eq!(rng.roll("2dd6"), Err(DiceError::Malformed));
+    assert_eq!(rng.roll("2d"), Err(DiceError::Malformed));
+    assert_eq!(rng.roll("2d6+"), Err(DiceError::Malformed));
+    assert_eq!(rng.roll("2d6+3+1"), Err(DiceError::Malformed));
+    assert_eq!(rng.roll("-2d6"), Err(DiceError::Malformed));
+    assert_eq!(rng.roll("six"), Err(DiceError::Malformed));
+    assert_eq!(rng.roll(""), Err(DiceError::Malformed));
+    assert_eq!(rng.roll("2d0"), Err(DiceError::NoSides));
+    assert_eq!(rng.roll("0d6"), Err(DiceError::NoDice));
+    assert_eq!(rng.roll("4294967295d6"), Err(DiceError::TooManyDice));
+    assert!((10_000..=60_000).contains(&rng.roll("10000d6").unwrap()));
+    assert_eq!(rng.roll("2d9223372036854775807+9223372036854775807"), Err(DiceError::Overflow));
+    assert_eq!(rng.roll("1d6+99999999999999999999"), Err(DiceError::Overflow));
+}
+
+#[test]
+pub fn test_decorrelated_counter() {
+    let parent_counter: u64 = u64::MAX - 12345;
+    let window: u64 = 1 << 32;
+
+    let mut starts: Vec<u64> = (0..2000).map(|id| SquaresRNG::decorrelated_counter(SquaresRNG::TEST_KEY, parent_counter, id)).collect();
+    starts.push(parent_counter);
+
+    // Sorted by distance from the parent, every stream should have at least a full window before the next one starts
+    let mut offsets: Vec<u64> = starts.iter().map(|start| start.wrapping_sub(parent_counter)).collect();
+    offsets.sort_unstable();
+    assert_eq!(offsets[0], 0);
+    assert!(offsets.windows(2).all(|pair| pair[1] - pair[0] >= window));
+    assert!(u64::MAX - offsets[offsets.len() - 1] >= window - 1);
+
+    // Nearby ids are scattered instead of being next to each other
+    let adjacent: usize = (0..1999).filter(|&i| starts[i + 1].wrapping_sub(starts[i]) == window).count();
+    println!("\nRNG Decorrelated Counter (Closer to 0): {}\n", adjacent);
+    assert!(adjacent < 5);
+
+    // Same inputs give the same counter, and streams really draw from their own windows
+    let counter: u64 = SquaresRNG::decorrelated_counter(SquaresRNG::TEST_KEY, parent_counter, 7);
+    assert_eq!(counter, starts[7]);
+    let mut parent: SquaresRNG = SquaresRNG::new_with_key(parent_counter, SquaresRNG::TEST_KEY);
+    let mut stream: SquaresRNG = SquaresRNG::new_with_key(counter, SquaresRNG::TEST_KEY);
+    let parent_values: Vec<u64> = parent.iter_u64().take(1000).collect();
+    let stream_values: Vec<u64> = stream.iter_u64().take(1000).collect();
+    assert_ne!(parent_values, stream_values);
+
+    assert_ne!(counter, SquaresRNG::decorrelated_counter(0x4a8dfb965c72184f, parent_counter, 7));
+}
+
+#[test]
+pub fn test_choose_weighted() {
+    let mut rng: SquaresRNG = SquaresRNG::new_with_key(0, SquaresRNG::TEST_KEY);
+    let items: [&str; 2] = ["common", "rare"];
+
+    let count: u64 = 1_000_000;
+    let mut second: u64 = 0;
+    for _ in 0..count {
+        if *rng.choose_weighted(&items, &[1.0, 3.0]).unwrap() == "rare" {
+            second += 1;
+        }
+    }
+    let second: f64 = second as f64 / count as f64;
+    println!("\nRNG Choose Weighted (Closer to 0.75): {}\n", second);
+    assert!((second - 0.75).abs() < 0.002);
+
+    for _ in 0..1000 {
+        assert_eq!(rng.choose_weighted(&items, &[0.0, 2.0]), Some(&"rare"));
+    }
+    assert_eq!(rng.choose_weighted(&items, &[1.0]), None);
+    assert_eq!(rng.choose_weighted::<&str>(&[], &[]), None);
+    assert_eq!(rng.choose_weighted(&items, &[0.0, 0.0]), None);
+    assert_eq!(rng.choose_weighted(&items, &[-1.0, -2.0]), None);
+    assert_eq!(rng.choose_weighted(&items, &[f64::NAN, 1.0]), None);
+}
+
+#[test]
+pub fn test_wang_tile_layout() {
+    // Every combination of two edge colors
+    let complete: WangTileSet = WangTileSet::new((0..16u8).map(|bits| [bits & 1, (bits >> 1) & 1, (bits >> 2) & 1, (bits >> 3) & 1]).collect());
+    // Horizontal edges only come in color 0 and vertical ones match their own tile, so tiles only fit in some places
+    let partial: WangTileSet = WangTileSet::new(vec![[0, 1, 0, 1], [0, 2, 0, 2], [0, 1, 0, 2], [0, 2, 0, 1]]);
+
+    for tileset in [&complete, &partial] {
+        let mut rng: SquaresRNG = SquaresRNG::new_with_key(0, SquaresRNG::TEST_KEY);
+        let grid: Vec<Vec<usize>> = rng.wang_tile_layout(tileset, 24, 16);
+        assert_eq!(grid.len(), 16);
+        assert!(grid.iter().all(|row| row.len() == 24));
+
+        for y in 0..16 {
+            for x in 0..24 {
+                let tile: [u8; 4] = tileset.tiles[grid[y][x]];
+                if x + 1 < 24 {
+                    assert_eq!(tile[WangTileSet::EAST], tileset.tiles[grid[y][x + 1]][WangTileSet::WEST]);
+                }
+                if y + 1 < 16 {
+                    assert_eq!(tile[WangTileSet::SOUTH], tileset.tiles[grid[y + 1][x]][WangTileSet::NORTH]);
+                }
+            }
+        }
+
+        let mut again: SquaresRNG = SquaresRNG::new_with_key(0, SquaresRNG::TEST_KEY);
+        assert_eq!(again.wang_tile_layout(tileset, 24, 16), grid);
+    }
+
+    let mut rng: SquaresRNG = SquaresRNG::new_with_key(0, SquaresRNG::TEST_KEY);
+    let grid: Vec<Vec<usize>> = rng.wang_tile_layout(&complete, 32, 32);
+    let mut used: Vec<u64> = vec![0; 16];
+    grid.iter().flatten().for_each(|&tile| used[tile] += 1);
+    println!("\nRNG Wang Tile Layout (Closer to 64 each): {:?}\n", used);
+    assert!(used.iter().all(|&count| count > 20));
+
+    // Nothing fits below a tile with a south edge of 1
+    let stuck: WangTileSet = WangTileSet::new(vec![[0, 0, 1, 0]]);
+    assert!(rng.wang_tile_layout(&stuck, 3, 3).is_empty());
+    assert_eq!(rng.wang_tile_layout(&stuck, 3, 1), vec![vec![0, 0, 0]]);
+}
+
+#[test]
+pub fn test_weighted_table() {
+    let mut rng: SquaresRNG = SquaresRNG::new_with_key(0, SquaresRNG::TEST_KEY);
+
+    let weights: [f64; 6] = [1.0, 0.0, 4.0, 2.5, 0.5, 2.0];
+    let table: WeightedTable = WeightedTable::new(&weights).unwrap();
+    assert_eq!(table.len(), 6);
+
+    let count: u64 = 2_000_000;
+    let mut hits: [u64; 6] = [0; 6];
+    for _ in 0..count {
+        hits[table.sample(&mut rng)] += 1;
+    }
+    assert_eq!(rng.counter, count);
+
+    let frequencies: Vec<f64> = hits.iter().map(|&hit| hit as f64 / count as f64).collect();
+    println!("\nRNG Weighted Table (Closer to [0.1, 0.0, 0.4, 0.25, 0.05, 0.2]): {:?}\n", frequencies);
+    for (frequency, weight) in frequencies.iter().zip(weights) {
+        assert!((frequency - weight / 10.0).abs() < 0.002);
+    }
+    assert_eq!(hits[1], 0);
+
+    let single: WeightedTable = WeightedTable::new(&[3.0]).unwrap();
+    assert!((0..1000).all(|_| single.sample(&mut rng) == 0));
+
+    assert_eq!(WeightedTable::new(&[]).err(), Some(WeightError::Empty));
+    assert_eq!(WeightedTable::new(&[0.0, 0.0]).err(), Some(WeightError::ZeroTotal));
+    assert_eq!(WeightedTable::new(&[1.0, -1.0]).err(), Some(WeightError::InvalidWeight));
+    assert_eq!(WeightedTable::new(&[1.0, f64::INFINITY]).err(), Some(WeightError::InvalidWeight));
+}
+
+#[test]
+pub fn test_default() {
+    let mut rng: SquaresRNG = SquaresRNG::default();
+    let mut expected: SquaresRNG = SquaresRNG::new_with_key(0, SquaresRNG::TEST_KEY);
+    assert_eq!((rng.counter, rng.key), (0, SquaresRNG::TEST_KEY));
+    assert!(SquaresRNG::validate_key(rng.key));
+
+    let values: Vec<u64> = rng.iter_u64().take(10).collect();
+    assert_eq!(values, expected.iter_u64().take(10).collect::<Vec<u64>>());
+    assert_eq!(SquaresRNG::default().iter_u64().take(10).collect::<Vec<u64>>(), values);
+}
+
+#[test]
+pub fn test_beat_triggers() {
+    let mut rng: SquaresRNG = SquaresRNG::new_with_key(0, SquaresRNG::TEST_KEY);
+
+    for (density, min_gap) in [(0.25, 1), (0.25, 2), (0.2, 4), (0.5, 1), (0.1, 3)] {
+        let triggers: Vec<bool> = rng.beat_triggers(200_000, density, min_gap);
+        assert_eq!(triggers.len(), 200_000);
+
+        let hits: Vec<usize> = (0..triggers.len()).filter(|&beat| triggers[beat]).collect();
+        assert!(hits.windows(2).all(|pair| pair[1] - pair[0] >= min_gap));
+
+        let fraction: f64 = hits.len() as f64 / triggers.len() as f64;
+        println!("\nRNG Beat Triggers (Closer to {}): {}\n", density, fraction);
+        assert!((fraction - density).abs() < 0.005);
+    }
+
+    // Too dense for the gap, so it triggers on every min_gap-th beat
+    let triggers: Vec<bool> = rng.beat_triggers(12, 0.9, 3);
+    assert_eq!(triggers, (0..12).map(|beat| beat % 3 == 0).collect::<Vec<bool>>());
+
+    assert!(rng.beat_triggers(100, 0.0, 2).iter().all(|&trigger| !trigger));
+    assert!(rng.beat_triggers(0, 0.5, 2).is_empty());
+}
+
+#[test]
+pub fn test_sample_dist_sized() {
+    let mut rng: SquaresRNG = SquaresRNG::new_with_key(0, SquaresRNG::TEST_KEY);
+    let items: Vec<u32> = (0..100).collect();
+
+    let count: u64 = 100_000;
+    let mut sum: f64 = 0.0;
+    let mut squares: f64 = 0.0;
+    for _ in 0..count {
+        let subset: Vec<&u32> = rng.sample_dist_sized(&items, &Distribution::Normal { mean: 30.0, stddev: 5.0 });
+        assert!(subset.windows(2).all(|pair| pair[0] < pair[1]));
+        sum += subset.len() as f64;
+        squares += (subset.len() * subset.len()) as f64;
+    }
+
+    // Rounding adds a variance of 1/12
+    let mean: f64 = sum / count as f64;
+    let deviation: f64 = (squares / count as f64 - mean * mean).sqrt();
+    println!("\nRNG Sample Dist Sized (Closer to 30.0 and 5.0083): mean: {}, deviation: {}\n", mean, deviation);
+    assert!((mean - 30.0).abs() < 0.05);
+    assert!((deviation - (25.0f64 + 1.0 / 12.0).sqrt()).abs() < 0.05);
+
+    let mut sizes: [u64; 4] = [0; 4];
+    for _ in 0..100_000 {
+        sizes[rng.sample_dist_sized(&items[..3], &Distribution::Uniform { min: -0.5, max: 10.0 }).len()] += 1;
+    }
+    assert!(sizes[3] > sizes[2] && sizes[2] > 0 && sizes[0] > 0);
+
+    assert_eq!(rng.sample_dist_sized(&items, &Distribution::Uniform { min: 1000.0, max: 2000.0 }).len(), 100);
+    assert!(rng.sample_dist_sized(&items, &Distribution::Exponential { lambda: -1.0 }).is_empty());
+}
+
+#[cfg(feature = "std")]
+#[test]
+pub fn test_from_entropy() {
+    let mut first: SquaresRNG = SquaresRNG::from_entropy();
+    let mut second: SquaresRNG = SquaresRNG::from_entropy();
+    println!("\nRNG From Entropy (Closer to different keys): {:x}, {:x}\n", first.key, second.key);
+
+    assert!(SquaresRNG::validate_key(first.key) && SquaresRNG::validate_key(second.key));
+    assert_ne!((first.key, first.counter), (second.key, second.counter));
+    assert_ne!(first.iter_u64().take(4).collect::<Vec<u64>>(), second.iter_u64().take(4).collect::<Vec<u64>>());
+}
+
+#[test]
+pub fn test_constrained_sequence() {
+    let mut rng: SquaresRNG = SquaresRNG::new_with_key(0, SquaresRNG::TEST_KEY);
+
+    // 0 is easy, 1 is medium, 2 is hard. Hard can't follow hard, and medium can't follow hard
+    let forbidden: [(usize, usize); 2] = [(2, 2), (2, 1)];
+    let weights: [f64; 3] = [5.0, 3.0, 2.0];
+    let sequence: Vec<usize> = rng.constrained_sequence(3, 1_000_000, &forbidden, &weights);
+    assert_eq!(sequence.len(), 1_000_000);
+    assert!(sequence.windows(2).all(|pair| !forbidden.contains(&(pair[0], pair[1]))));
+
+    // Every state after 0 or 1 is picked by weight, and after 2 it is always 0
+    let mut counts: [f64; 3] = [0.0; 3];
+    sequence.iter().for_each(|&state| counts[state] += 1.0);
+    let total: f64 = sequence.len() as f64;
+    let hard: f64 = 0.2 / 1.2;
+    let expected: [f64; 3] = [1.0 - hard - 0.3 * (1.0 - hard), 0.3 * (1.0 - hard), hard];
+    println!("\nRNG Constrained Sequence (Closer to {:?}): {:?}\n", expected, counts.map(|count| count / total));
+    for (count, expected) in counts.iter().zip(expected) {
+        assert!((count / total - expected).abs() < 0.002);
+    }
+
+    // Unconstrained sequences follow the weights directly
+    let free: Vec<usize> = rng.constrained_sequence(3, 1_000_000, &[], &weights);
+    assert!((free.iter().filter(|&&state| state == 0).count() as f64 / 1_000_000.0 - 0.5).abs() < 0.002);
+
+    // 1 can't be followed by anything, so it only shows up last
+    let dead_end: [(usize, usize); 2] = [(1, 0), (1, 1)];
+    for _ in 0..100 {
+        let sequence: Vec<usize> = rng.constrained_sequence(2, 5, &dead_end, &[1.0, 1.0]);
+        assert_eq!(sequence.len(), 5);
+        assert!(sequence[..4].iter().all(|&state| state == 0));
+    }
+
+    assert!(rng.constrained_sequence(2, 5, &[(0, 0), (0, 1), (1, 0), (1, 1)], &[1.0, 1.0]).is_empty());
+    assert!(rng.constrained_sequence(3, 5, &[], &[1.0, 1.0]).is_empty());
+    assert!(rng.constrained_sequence(2, 5, &[], &[0.0, 0.0]).is_empty());
+}
+
+#[test]
+pub fn test_hex_random_walk() {
+    let mut rng: SquaresRNG = SquaresRNG::new_with_key(0, SquaresRNG::TEST_KEY);
+
+    let path: Vec<(i32, i32)> = rng.hex_random_walk((3, -7), 600_000);
+    assert_eq!(path.len(), 600_001);
+    assert_eq!(path[0], (3, -7));
+
+    let mut directions: [u64; 6] = [0; 6];
+    for pair in path.windows(2) {
+        let (dq, dr) = (pair[1].0 - pair[0].0, pair[1].1 - pair[0].1);
+        // Hex distance between neighbors is always 1
+        assert_eq!((dq.abs() + dr.abs() + (dq + dr).abs()) / 2, 1);
+        let direction: usize = [(1, 0), (1, -1), (0, -1), (-1, 0), (-1, 1), (0, 1)].iter().position(|&step| step == (dq, dr)).unwrap();
+        directions[direction] += 1;
+    }
+    println!("\nRNG Hex Random Walk (Closer to 100000 each): {:?}\n", directions);
+    assert!(directions.iter().all(|&count| (99_000..101_000).contains(&count)));
+
+    assert_eq!(rng.hex_random_walk((0, 0), 0), vec![(0, 0)]);
+}
+
+#[test]
+pub fn test_fork() {
+    let mut rng: SquaresRNG = SquaresRNG::new_with_key(0, SquaresRNG::TEST_KEY);
+    let mut children: Vec<SquaresRNG> = (0..3).map(|_| rng.fork()).collect();
+    assert_eq!(rng.counter, 6);
+
+    let firsts: Vec<u64> = children.iter_mut().map(|child| child.rand_u64()).collect();
+    println!("\nRNG Fork (Closer to different values): {:?}\n", firsts);
+    assert!(firsts[0] != firsts[1] && firsts[1] != firsts[2] && firsts[0] != firsts[2]);
+    assert!(children.iter().all(|child| child.counter == 2 && SquaresRNG::validate_key(child.key)));
+
+    let mut again: SquaresRNG = SquaresRNG::new_with_key(0, SquaresRNG::TEST_KEY);
+    let repeat: Vec<u64> = (0..3).map(|_| again.fork().rand_u64()).collect();
+    assert_eq!(firsts, repeat);
+}
+
+#[test]
+pub fn test_generate_dungeon() {
+    let spec: DungeonSpec = DungeonSpec::new(80, 60, 12, 0.05);
+
+    let dungeon: Dungeon = SquaresRNG::generate_dungeon(42, &spec);
+    assert_eq!(SquaresRNG::generate_dungeon(42, &spec), dungeon);
+    assert_ne!(SquaresRNG::generate_dungeon(43, &spec), dungeon);
+
+    let mut room_cells: u64 = 0;
+    let mut loot: u64 = 0;
+    for seed in 0..50 {
+        let dungeon: Dungeon = SquaresRNG::generate_dungeon(seed, &spec);
+        assert!(!dungeon.rooms.is_empty() && dungeon.rooms.len() <= spec.rooms);
+
+        for (i, &(x, y, w, h)) in dungeon.rooms.iter().enumerate() {
+            assert!((spec.min_room..=spec.max_room).contains(&w) && (spec.min_room..=spec.max_room).contains(&h));
+            assert!(x + w <= spec.width && y + h <= spec.height);
+            for &(ox, oy, ow, oh) in dungeon.rooms[i + 1..].iter() {
+                assert!(x >= ox + ow || ox >= x + w || y >= oy + oh || oy >= y + h);
+            }
+            room_cells += (w * h) as u64;
+        }
+
+        // Spread reachability along corridors until nothing new is found
+        let mut reached: Vec<bool> = vec![false; dungeon.rooms.len()];
+        reached[0] = true;
+        for _ in 0..dungeon.rooms.len() {
+            for &(a, b) in dungeon.corridors.iter() {
+                if reached[a] || reached[b] {
+                    reached[a] = true;
+                    reached[b] = true;
+                }
+            }
+        }
+        assert!(reached.iter().all(|&reached| reached));
+
+        for &(lx, ly) in dungeon.loot.iter() {
+            assert!(dungeon.rooms.iter().any(|&(x, y, w, h)| lx >= x && lx < x + w && ly >= y && ly < y + h));
+        }
+        loot += dungeon.loot.len() as u64;
+    }
+
+    let density: f64 = loot as f64 / room_cells as f64;
+    println!("\nRNG Generate Dungeon (Closer to 0.05): {}\n", density);
+    assert!((density - 0.05).abs() < 0.005);
+
+    let empty: Dungeon = SquaresRNG::generate_dungeon(42, &DungeonSpec::new(3, 3, 5, 0.5));
+    assert!(empty.rooms.is_empty() && empty.corridors.is_empty() && empty.loot.is_empty());
+}
+
+#[test]
+pub fn test_in_circle_f32() {
+    let mut rng: SquaresRNG = SquaresRNG::new_with_key(0, SquaresRNG::TEST_KEY);
+
+    // Rings with radii at sqrt(i / 5) all have the same area
+    let count: u64 = 1_000_000;
+    let mut rings: [u64; 5] = [0; 5];
+    let mut sum: (f64, f64) = (0.0, 0.0);
+    for _ in 0..count {
+        let (x, y) = rng.in_unit_circle_f32();
+        let squared: f32 = x * x + y * y;
+        assert!(squared <= 1.0);
+        rings[((squared * 5.0) as usize).min(4)] += 1;
+        sum = (sum.0 + x as f64, sum.1 + y as f64);
+    }
+    println!("\nRNG In Unit Circle (Closer to 200000 each): {:?}\n", rings);
+    assert!(rings.iter().all(|&ring| (198_000..202_000).contains(&ring)));
+    assert!((sum.0 / count as f64).abs() < 0.002 && (sum.1 / count as f64).abs() < 0.002);
+
+    let mut inner: u64 = 0;
+    for _ in 0..count {
+        let (x, y) = rng.in_circle_f32(8.0);
+        let distance: f32 = (x * x + y * y).sqrt();
+        assert!(distance <= 8.0 + 0.0001);
+        if distance < 4.0 {
+            inner += 1;
+        }
+    }
+    assert!((inner as f64 / count as f64 - 0.25).abs() < 0.002);
+}
+
+#[test]
+pub fn test_color_rgb8() {
+    let mut rng: SquaresRNG = SquaresRNG::new_with_key(0, SquaresRNG::TEST_KEY);
+
+    let count: u64 = 1_000_000;
+    let mut seen: [[u64; 256]; 3] = [[0; 256]; 3];
+    for _ in 0..count {
+        let (r, g, b) = rng.color_rgb8();
+        seen[0][r as usize] += 1;
+        seen[1][g as usize] += 1;
+        seen[2][b as usize] += 1;
+    }
+    assert_eq!(rng.counter, count);
+
+    let (fewest, most): (u64, u64) = (*seen.iter().flatten().min().unwrap(), *seen.iter().flatten().max().unwrap());
+    println!("\nRNG Color RGB8 (Closer to 3906 each): fewest: {}, most: {}\n", fewest, most);
+    assert!(fewest > 3600 && most < 4200);
+}
+
+#[test]
+pub fn test_color_hsv() {
+    let mut rng: SquaresRNG = SquaresRNG::new_with_key(0, SquaresRNG::TEST_KEY);
+
+    let mut sextants: [u64; 6] = [0; 6];
+    for _ in 0..600_000 {
+        let (hue, s, v) = rng.color_hsv(0.8, 0.9);
+        assert!((0.0..360.0).contains(&hue));
+        assert_eq!((s, v), (0.8, 0.9));
+        sextants[(hue / 60.0) as usize] += 1;
+    }
+    println!("\nRNG Color HSV (Closer to 100000 each): {:?}\n", sextants);
+    assert!(sextants.iter().all(|&count| (99_000..101_000).contains(&count)));
+}
+
+#[test]
+pub fn test_range() {
+    let mut rng: SquaresRNG = SquaresRNG::new_with_key(0, SquaresRNG::TEST_KEY);
+
+    let mut bytes: [u64; 256] = [0; 256];
+    for _ in 0..2_560_000 {
+        bytes[rng.range::<u8>(0, 255) as usize] += 1;
+    }
+    println!("\nRNG Range u8 (Closer to 10000 each): fewest: {}, most: {}\n", bytes.iter().min().unwrap(), bytes.iter().max().unwrap());
+    assert!(bytes.iter().all(|&count| (9_500..10_500).contains(&count)));
+
+    let mut shorts: Vec<u64> = vec![0; 65536];
+    for _ in 0..6_553_600 {
+        shorts[(rng.range::<i16>(i16::MAX, i16::MIN) as i32 - i16::MIN as i32) as usize] += 1;
+    }
+    assert!(shorts[0] > 0 && shorts[65535] > 0);
+    assert!(shorts.iter().all(|&count| (40..170).contains(&count)));
+    let halves: (u64, u64) = (shorts[..32768].iter().sum(), shorts[32768..].iter().sum());
+    assert!((halves.0 as f64 / halves.1 as f64 - 1.0).abs() < 0.005);
+
+    // A full u64 span uses every bit, and a narrow span at the top reaches both ends
+    let mut top_bits: [u64; 16] = [0; 16];
+    for _ in 0..1_600_000 {
+        top_bits[(rng.range::<u64>(0, u64::MAX) >> 60) as usize] += 1;
+    }
+    assert!(top_bits.iter().all(|&count| (98_000..102_000).contains(&count)));
+    let ends: Vec<u64> = (0..1000).map(|_| rng.range::<u64>(u64::MAX - 3, u64::MAX)).collect();
+    assert!(ends.contains(&u64::MAX) && ends.contains(&(u64::MAX - 3)));
+    assert!(ends.iter().all(|&value| value >= u64::MAX - 3));
+
+    // rangei32 and rangei64 are the same as range for their types
+    let mut direct: SquaresRNG = SquaresRNG::new_with_key(rng.counter, rng.key);
+    assert_eq!(rng.range::<i32>(-50, 50), direct.rangei32(-50, 50));
+    assert_eq!(rng.range::<i64>(i64::MIN, i64::MAX), direct.rangei64(i64::MIN, i64::MAX));
+    assert_eq!(rng.range::<usize>(7, 7), 7);
+}
+
+#[test]
+pub fn test_gen_array() {
+    let mut rng: SquaresRNG = SquaresRNG::new_with_key(0, SquaresRNG::TEST_KEY);
+    let mut direct: SquaresRNG = SquaresRNG::new_with_key(0, SquaresRNG::TEST_KEY);
+
+    let floats: [f32; 4] = rng.gen_array_f32::<4>();
+    assert_eq!(floats, [direct.randf32(), direct.randf32(), direct.randf32(), direct.randf32()]);
+
+    let values: [u64; 3] = rng.gen_array_u64();
+    assert_eq!(values, [direct.rand_u64(), direct.rand_u64(), direct.rand_u64()]);
+
+    let empty: [f32; 0] = rng.gen_array_f32();
+    assert!(empty.is_empty());
+    assert_eq!(rng.counter, 10);
+}
+
+#[test]
+pub fn test_triangular_f32() {
+    let mut rng: SquaresRNG = SquaresRNG::new_with_key(0, SquaresRNG::TEST_KEY);
+
+    for (min, max, mode) in [(10.0f32, 20.0f32, 12.0f32), (-1.0, 1.0, 0.0), (0.0, 5.0, 5.0), (0.0, 5.0, 0.0)] {
+        let count: u64 = 1_000_000;
+        let mut sum: f64 = 0.0;
+        for _ in 0..count {
+            let value: f32 = rng.triangular_f32(min, max, mode);
+            assert!((min..=max).contains(&value));
+            sum += value as f64;
+        }
+
+        let mean: f64 = sum / count as f64;
+        let expected: f64 = (min + max + mode) as f64 / 3.0;
+        println!("\nRNG Triangular f32 (Closer to {}): {}\n", expected, mean);
+        assert!((mean - expected).abs() < 0.01 * (max - min) as f64);
+    }
+
+    // Modes outside the range sample like the clamped mode
+    for (min, max, mode) in [(0.0f32, 5.0f32, 40.0f32), (0.0, 5.0, -40.0), (-2.0, 2.0, 3.0)] {
+        let count: u64 = 1_000_000;
+        let mut sum: f64 = 0.0;
+        for _ in 0..count {
+            let value: f32 = rng.triangular_f32(min, max, mode);
+            assert!((min..=max).contains(&value));
+            sum += value as f64;
+        }
+
+        let mean: f64 = sum / count as f64;
+        let expected: f64 = (min + max + mode.clamp(min, max)) as f64 / 3.0;
+        println!("\nRNG Triangular f32 Clamped Mode (Closer to {}): {}\n", expected, mean);
+        assert!((mean - expected).abs() < 0.01 * (max - min) as f64);
+    }
+
+    assert!(rng.triangular_f32(10.0, 0.0, 5.0).is_nan());
+    assert_eq!(rng.triangular_f32(3.0, 3.0, 9.0), 3.0);
+    assert_eq!(rng.triangular_f32(3.0, 3.0, 3.0), 3.0);
+}